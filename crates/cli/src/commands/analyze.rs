@@ -1,10 +1,11 @@
 use anyhow::Result;
 use mccabre_core::{
-    cloner::CloneDetector,
+    cloner::{CloneDetector, DetectionMode},
     complexity::{CyclomaticMetrics, LocMetrics},
     config::Config,
-    loader::{FileLoader, SourceFile},
+    loader::{ChangeScope, FileLoader, SourceFile},
     reporter::{FileReport, Report},
+    tokenizer::Language,
 };
 use owo_colors::OwoColorize;
 use std::collections::HashMap;
@@ -12,19 +13,47 @@ use std::path::PathBuf;
 
 use crate::highlight::Highlighter;
 
+#[allow(clippy::too_many_arguments)]
 pub fn run(
     path: PathBuf, json: bool, threshold: Option<usize>, min_tokens: Option<usize>, config_path: Option<PathBuf>,
-    respect_gitignore: bool, highlight: bool,
+    respect_gitignore: bool, respect_ignore_file: bool, highlight: bool, theme: Option<String>, include: Vec<String>,
+    exclude: Vec<String>, r#type: Vec<String>, type_not: Vec<String>, threads: usize, since: Option<String>,
+    staged: bool, detection_mode: Option<String>, winnow_window: Option<usize>,
 ) -> Result<()> {
     let config = if let Some(config_path) = config_path {
         Config::from_file(config_path)?
     } else {
-        Config::load_default()?
+        Config::load_for_path(&path)?
+    };
+    let config = config.apply_env()?;
+
+    let config = config.merge_with_cli(
+        threshold,
+        min_tokens,
+        Some(respect_gitignore),
+        Some(respect_ignore_file),
+        (!include.is_empty()).then_some(include),
+        (!exclude.is_empty()).then_some(exclude),
+        (!r#type.is_empty()).then_some(r#type),
+        (!type_not.is_empty()).then_some(type_not),
+        detection_mode,
+        winnow_window,
+    );
+    let loader = FileLoader::new()
+        .with_gitignore(config.files.respect_gitignore)
+        .with_ignore_file(config.files.respect_ignore_file)
+        .with_includes(&config.files.include)?
+        .with_excludes(&config.files.exclude)?
+        .with_languages(Language::parse_many(&config.files.languages)?)
+        .without_languages(Language::parse_many(&config.files.languages_exclude)?)
+        .with_threads(threads);
+    let files = if staged {
+        loader.load_changed(&path, ChangeScope::Staged)?
+    } else if let Some(since) = since {
+        loader.load_changed(&path, ChangeScope::Since(since))?
+    } else {
+        loader.load(&path)?
     };
-
-    let config = config.merge_with_cli(threshold, min_tokens, Some(respect_gitignore));
-    let loader = FileLoader::new().with_gitignore(config.files.respect_gitignore);
-    let files = loader.load(&path)?;
 
     if files.is_empty() {
         eprintln!("{}", "No supported files found".yellow());
@@ -41,7 +70,13 @@ pub fn run(
     }
 
     let clones = if config.clones.enabled {
-        let detector = CloneDetector::new(config.clones.min_tokens);
+        let mode = DetectionMode::parse_name(&config.clones.detection_mode).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Invalid detection_mode '{}'. Use: exact or normalized",
+                config.clones.detection_mode
+            )
+        })?;
+        let detector = CloneDetector::new(config.clones.min_tokens).with_mode(mode).with_winnow_window(config.clones.winnow_window);
         let files_for_clone_detection: Vec<_> = files
             .iter()
             .map(|f| (f.path.clone(), f.content.clone(), f.language))
@@ -56,13 +91,15 @@ pub fn run(
     if json {
         println!("{}", report.to_json()?);
     } else {
-        print_pretty_report(&report, &config, &files, highlight);
+        print_pretty_report(&report, &config, &files, highlight, theme.as_deref())?;
     }
 
     Ok(())
 }
 
-fn print_pretty_report(report: &Report, config: &Config, files: &[SourceFile], highlight: bool) {
+fn print_pretty_report(
+    report: &Report, config: &Config, files: &[SourceFile], highlight: bool, theme: Option<&str>,
+) -> Result<()> {
     println!("{}", "=".repeat(80).cyan());
     println!("{}", "MCCABRE CODE ANALYSIS REPORT".cyan().bold());
     println!("{}", "=".repeat(80).cyan());
@@ -140,7 +177,14 @@ fn print_pretty_report(report: &Report, config: &Config, files: &[SourceFile], h
         println!("{}", "-".repeat(80).cyan());
 
         let file_map: HashMap<_, _> = files.iter().map(|f| (&f.path, f)).collect();
-        let highlighter = if highlight { Some(Highlighter::new()) } else { None };
+        let highlighter = if highlight {
+            Some(match theme {
+                Some(name) => Highlighter::with_theme(name)?,
+                None => Highlighter::new(),
+            })
+        } else {
+            None
+        };
 
         for clone in &report.clones {
             println!(
@@ -181,6 +225,8 @@ fn print_pretty_report(report: &Report, config: &Config, files: &[SourceFile], h
     }
 
     println!("{}", "=".repeat(80).cyan());
+
+    Ok(())
 }
 
 /// Extract lines from source code by line numbers (1-indexed)
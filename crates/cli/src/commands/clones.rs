@@ -1,9 +1,10 @@
 use anyhow::Result;
 use mccabre_core::{
-    cloner::CloneDetector,
+    cloner::{CloneDetector, DetectionMode},
     config::Config,
-    loader::{FileLoader, SourceFile},
+    loader::{ChangeScope, FileLoader, SourceFile},
     reporter::Report,
+    tokenizer::Language,
 };
 use owo_colors::OwoColorize;
 use std::collections::HashMap;
@@ -11,26 +12,60 @@ use std::path::PathBuf;
 
 use crate::highlight::Highlighter;
 
+#[allow(clippy::too_many_arguments)]
 pub fn run(
     path: PathBuf, json: bool, min_tokens: Option<usize>, config_path: Option<PathBuf>, respect_gitignore: bool,
-    highlight: bool,
+    respect_ignore_file: bool, highlight: bool, theme: Option<String>, context_lines: usize, highlight_renames: bool,
+    include: Vec<String>, exclude: Vec<String>, r#type: Vec<String>, type_not: Vec<String>, threads: usize,
+    since: Option<String>, staged: bool, detection_mode: Option<String>, winnow_window: Option<usize>,
 ) -> Result<()> {
     let config = if let Some(config_path) = config_path {
         Config::from_file(config_path)?
     } else {
-        Config::load_default()?
+        Config::load_for_path(&path)?
+    };
+    let config = config.apply_env()?;
+
+    let config = config.merge_with_cli(
+        None,
+        min_tokens,
+        Some(respect_gitignore),
+        Some(respect_ignore_file),
+        (!include.is_empty()).then_some(include),
+        (!exclude.is_empty()).then_some(exclude),
+        (!r#type.is_empty()).then_some(r#type),
+        (!type_not.is_empty()).then_some(type_not),
+        detection_mode,
+        winnow_window,
+    );
+    let loader = FileLoader::new()
+        .with_gitignore(config.files.respect_gitignore)
+        .with_ignore_file(config.files.respect_ignore_file)
+        .with_includes(&config.files.include)?
+        .with_excludes(&config.files.exclude)?
+        .with_languages(Language::parse_many(&config.files.languages)?)
+        .without_languages(Language::parse_many(&config.files.languages_exclude)?)
+        .with_threads(threads);
+    let files = if staged {
+        loader.load_changed(&path, ChangeScope::Staged)?
+    } else if let Some(since) = since {
+        loader.load_changed(&path, ChangeScope::Since(since))?
+    } else {
+        loader.load(&path)?
     };
-
-    let config = config.merge_with_cli(None, min_tokens, Some(respect_gitignore));
-    let loader = FileLoader::new().with_gitignore(config.files.respect_gitignore);
-    let files = loader.load(&path)?;
 
     if files.is_empty() {
         eprintln!("{}", "No supported files found".yellow());
         return Ok(());
     }
 
-    let detector = CloneDetector::new(config.clones.min_tokens);
+    let mode = DetectionMode::parse_name(&config.clones.detection_mode).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Invalid detection_mode '{}'. Use: exact or normalized",
+            config.clones.detection_mode
+        )
+    })?;
+    let detector = CloneDetector::new(config.clones.min_tokens).with_mode(mode).with_winnow_window(config.clones.winnow_window);
     let files_for_clone_detection: Vec<_> = files
         .iter()
         .map(|f| (f.path.clone(), f.content.clone(), f.language))
@@ -42,13 +77,16 @@ pub fn run(
     if json {
         println!("{}", report.to_json()?);
     } else {
-        print_clones_report(&report, &files, highlight);
+        print_clones_report(&report, &files, highlight, theme.as_deref(), context_lines, highlight_renames)?;
     }
 
     Ok(())
 }
 
-fn print_clones_report(report: &Report, files: &[SourceFile], highlight: bool) {
+fn print_clones_report(
+    report: &Report, files: &[SourceFile], highlight: bool, theme: Option<&str>, context_lines: usize,
+    highlight_renames: bool,
+) -> Result<()> {
     println!("{}", "=".repeat(80).cyan());
     println!("{}", "CLONE DETECTION REPORT".cyan().bold());
     println!("{}\n", "=".repeat(80).cyan());
@@ -65,7 +103,14 @@ fn print_clones_report(report: &Report, files: &[SourceFile], highlight: bool) {
         println!();
 
         let file_map: HashMap<_, _> = files.iter().map(|f| (&f.path, f)).collect();
-        let highlighter = if highlight { Some(Highlighter::new()) } else { None };
+        let highlighter = if highlight {
+            Some(match theme {
+                Some(name) => Highlighter::with_theme(name)?,
+                None => Highlighter::new(),
+            })
+        } else {
+            None
+        };
 
         for clone in &report.clones {
             println!(
@@ -87,15 +132,28 @@ fn print_clones_report(report: &Report, files: &[SourceFile], highlight: bool) {
                 );
 
                 if highlight && let Some(source_file) = file_map.get(&loc.file) {
-                    let code_block = extract_lines(&source_file.content, loc.start_line, loc.end_line);
+                    let window = extract_window(&source_file.content, loc.start_line, loc.end_line, context_lines);
 
                     if let Some(ref hl) = highlighter {
                         let ext = source_file.path.extension().and_then(|e| e.to_str()).unwrap_or("txt");
-                        let highlighted = hl.highlight(&code_block, ext);
+                        let highlighted = if highlight_renames {
+                            hl.highlight_with_identifier_colors(&window.code, ext, source_file.language)?
+                        } else {
+                            hl.highlight(&window.code, ext)
+                        };
+                        let last_line = window.start + highlighted.lines().count().saturating_sub(1);
+                        let gutter_width = last_line.to_string().len();
 
                         println!("{}", "    ┌─────".dimmed());
-                        for line in highlighted.lines() {
-                            println!("    │ {line}");
+                        for (idx, line) in highlighted.lines().enumerate() {
+                            let line_num = window.start + idx;
+                            let gutter = format!("{line_num:>gutter_width$}");
+
+                            if line_num < loc.start_line || line_num > loc.end_line {
+                                println!("    │ {} │ {}", gutter.dimmed(), line.dimmed());
+                            } else {
+                                println!("    │ {gutter} │ {line}");
+                            }
                         }
                         println!("{}", "    └─────".dimmed());
                     }
@@ -106,18 +164,34 @@ fn print_clones_report(report: &Report, files: &[SourceFile], highlight: bool) {
     }
 
     println!("{}", "=".repeat(80).cyan());
+
+    Ok(())
+}
+
+/// A grep-style `-C`-equivalent window: the clone's own lines plus
+/// `context_lines` before/after, clamped to the file's extent.
+struct Window {
+    code: String,
+    /// 1-indexed line number of the first line in `code`
+    start: usize,
 }
 
-/// Extract lines from source code by line numbers (1-indexed)
-fn extract_lines(source: &str, start_line: usize, end_line: usize) -> String {
-    source
+/// Extract a window of lines from source code by line numbers (1-indexed)
+fn extract_window(source: &str, start_line: usize, end_line: usize, context_lines: usize) -> Window {
+    let total_lines = source.lines().count();
+    let window_start = start_line.saturating_sub(context_lines).max(1);
+    let window_end = (end_line + context_lines).min(total_lines);
+
+    let code = source
         .lines()
         .enumerate()
         .filter(|(idx, _)| {
             let line_num = idx + 1;
-            line_num >= start_line && line_num <= end_line
+            line_num >= window_start && line_num <= window_end
         })
         .map(|(_, line)| line)
         .collect::<Vec<_>>()
-        .join("\n")
+        .join("\n");
+
+    Window { code, start: window_start }
 }
@@ -0,0 +1,91 @@
+use anyhow::Result;
+use mccabre_core::compare::{Baseline, RegressionPolicy, RegressionReport, check_regressions};
+use owo_colors::OwoColorize;
+use std::path::PathBuf;
+
+pub fn run(
+    baseline_path: PathBuf, current_path: PathBuf, max_coverage_drop: Option<f64>, fail_on_new_misses: bool,
+    json: bool,
+) -> Result<()> {
+    let baseline = Baseline::load(&baseline_path)?;
+    let current = Baseline::load(&current_path)?;
+
+    let policy = RegressionPolicy { max_coverage_drop_pct: max_coverage_drop, fail_on_new_misses };
+    let report = check_regressions(&baseline, &current, &policy);
+
+    if json {
+        println!("{}", report.to_json()?);
+    } else {
+        print_regression_report(&report);
+    }
+
+    if !report.passed {
+        anyhow::bail!("regression check failed: {} issue(s) found", report.failures.len());
+    }
+
+    Ok(())
+}
+
+fn print_regression_report(report: &RegressionReport) {
+    println!("{}", "=".repeat(80).cyan());
+    println!("{}", "REGRESSION COMPARISON".cyan().bold());
+    println!("{}\n", "=".repeat(80).cyan());
+
+    println!("{}", "SUMMARY".green().bold());
+    println!("{}", "-".repeat(80).cyan());
+    println!(
+        "Total logical LOC change:    {}",
+        report.total_logical_change.bold()
+    );
+    println!(
+        "Coverage rate before:        {}",
+        format!("{:.2}%", report.coverage_rate_before).bold()
+    );
+    println!(
+        "Coverage rate after:         {}\n",
+        format!("{:.2}%", report.coverage_rate_after).bold()
+    );
+
+    if !report.loc_deltas.is_empty() {
+        println!("{}", "LOC CHANGES".green().bold());
+        println!("{}", "-".repeat(80).cyan());
+        for delta in &report.loc_deltas {
+            if delta.logical_change != 0 {
+                println!(
+                    "  {} logical {:+}",
+                    delta.path.display().bold(),
+                    delta.logical_change
+                );
+            }
+        }
+        println!();
+    }
+
+    if !report.coverage_deltas.is_empty() {
+        println!("{}", "COVERAGE CHANGES".green().bold());
+        println!("{}", "-".repeat(80).cyan());
+        for delta in &report.coverage_deltas {
+            if delta.miss_change != 0 {
+                println!("  {} misses {:+}", delta.path.bold(), delta.miss_change);
+            }
+        }
+        println!();
+    }
+
+    if !report.failures.is_empty() {
+        println!("{}", "FAILURES".red().bold());
+        println!("{}", "-".repeat(80).cyan());
+        for failure in &report.failures {
+            println!("  - {}", failure.red());
+        }
+        println!();
+    }
+
+    if report.passed {
+        println!("{}", "PASSED".green().bold());
+    } else {
+        println!("{}", "FAILED".red().bold());
+    }
+
+    println!("{}", "=".repeat(80).cyan());
+}
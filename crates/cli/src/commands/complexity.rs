@@ -1,25 +1,118 @@
 use anyhow::Result;
+use clap::ValueEnum;
 use mccabre_core::{
     complexity::{CyclomaticMetrics, LocMetrics},
     config::Config,
-    loader::FileLoader,
-    reporter::{FileReport, Report},
+    coverage::{VfsPath, parse_and_merge},
+    loader::{ChangeScope, FileLoader, SourceFile},
+    reporter::{FileReport, Report, find_hotspots, report_hotspots},
+    tokenizer::Language,
 };
 use owo_colors::OwoColorize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+/// How the complexity command should render its results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum OutputFormat {
+    /// Colored, human-readable report (the default)
+    Text,
+    /// The crate's bespoke `Report` JSON shape
+    Json,
+    /// GitHub Actions workflow-command annotations for inline PR review
+    Github,
+    /// SARIF 2.1.0 document for code-scanning ingestion
+    Sarif,
+}
+
+/// Which complexity level, if any, should cause the process to exit non-zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum FailOn {
+    /// Exit 0 regardless of complexity (the default)
+    Never,
+    /// Fail if any function reaches the warning threshold or above
+    Warning,
+    /// Fail only if any function reaches the error threshold
+    Error,
+}
+
+impl FailOn {
+    /// Human-readable label for the footer/error message.
+    fn label(self) -> &'static str {
+        match self {
+            Self::Never => "never",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// Count functions across `report` whose complexity breaches the threshold
+/// selected by `fail_on` (resolved per file via [`ComplexityConfig::thresholds_for`]).
+/// Always `0` when `fail_on` is [`FailOn::Never`].
+fn count_breaches(report: &Report, config: &Config, fail_on: FailOn) -> usize {
+    report
+        .files
+        .iter()
+        .map(|file| {
+            let language = Language::from_path(&file.path).ok();
+            let (warning_threshold, error_threshold) = config.complexity.thresholds_for(&file.path, language);
+
+            file.cyclomatic
+                .functions
+                .iter()
+                .filter(|func| match fail_on {
+                    FailOn::Never => false,
+                    FailOn::Warning => func.complexity > warning_threshold,
+                    FailOn::Error => func.complexity > error_threshold,
+                })
+                .count()
+        })
+        .sum()
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn run(
-    path: PathBuf, json: bool, threshold: Option<usize>, config_path: Option<PathBuf>, respect_gitignore: bool,
+    path: PathBuf, format: OutputFormat, threshold: Option<usize>, config_path: Option<PathBuf>,
+    respect_gitignore: bool, respect_ignore_file: bool, include: Vec<String>, exclude: Vec<String>,
+    r#type: Vec<String>, type_not: Vec<String>, threads: usize, since: Option<String>, staged: bool,
+    fail_on: FailOn, coverage: Vec<PathBuf>, hotspot_coverage_threshold: f64,
 ) -> Result<()> {
     let config = if let Some(config_path) = config_path {
         Config::from_file(config_path)?
     } else {
-        Config::load_default()?
+        Config::load_for_path(&path)?
     };
+    let config = config.apply_env()?;
 
-    let config = config.merge_with_cli(threshold, None, Some(respect_gitignore));
-    let loader = FileLoader::new().with_gitignore(config.files.respect_gitignore);
-    let files = loader.load(&path)?;
+    let config = config.merge_with_cli(
+        threshold,
+        None,
+        Some(respect_gitignore),
+        Some(respect_ignore_file),
+        (!include.is_empty()).then_some(include),
+        (!exclude.is_empty()).then_some(exclude),
+        (!r#type.is_empty()).then_some(r#type),
+        (!type_not.is_empty()).then_some(type_not),
+        None,
+        None,
+    );
+    let loader = FileLoader::new()
+        .with_gitignore(config.files.respect_gitignore)
+        .with_ignore_file(config.files.respect_ignore_file)
+        .with_includes(&config.files.include)?
+        .with_excludes(&config.files.exclude)?
+        .with_languages(Language::parse_many(&config.files.languages)?)
+        .without_languages(Language::parse_many(&config.files.languages_exclude)?)
+        .with_threads(threads);
+    let files = if staged {
+        loader.load_changed(&path, ChangeScope::Staged)?
+    } else if let Some(since) = since {
+        loader.load_changed(&path, ChangeScope::Since(since))?
+    } else {
+        loader.load(&path)?
+    };
 
     if files.is_empty() {
         eprintln!("{}", "No supported files found".yellow());
@@ -36,17 +129,110 @@ pub fn run(
     }
 
     let report = Report::new(file_reports, Vec::new());
+    let breach_count = count_breaches(&report, &config, fail_on);
 
-    if json {
-        println!("{}", report.to_json()?);
-    } else {
-        print_complexity_report(&report, &config);
+    match format {
+        OutputFormat::Json => println!("{}", report.to_json()?),
+        OutputFormat::Sarif => {
+            println!(
+                "{}",
+                report.to_sarif(config.complexity.warning_threshold, config.complexity.error_threshold)?
+            )
+        }
+        OutputFormat::Github => print_github_annotations(&report, &config),
+        OutputFormat::Text => print_complexity_report(&report, &config, fail_on, breach_count),
+    }
+
+    if !coverage.is_empty() {
+        print_hotspots(&files, &config, &coverage, hotspot_coverage_threshold)?;
+    }
+
+    if breach_count > 0 && !matches!(fail_on, FailOn::Never) {
+        anyhow::bail!(
+            "{breach_count} function(s) breached the {} complexity threshold (--fail-on={})",
+            fail_on.label(),
+            fail_on.label()
+        );
+    }
+
+    Ok(())
+}
+
+/// Correlate each loaded file's coverage (parsed/merged from `coverage`)
+/// against its cyclomatic complexity, printing a worst-first table of
+/// high-complexity, low-coverage functions per file.
+fn print_hotspots(
+    files: &[SourceFile], config: &Config, coverage: &[PathBuf], hotspot_coverage_threshold: f64,
+) -> Result<()> {
+    let coverage_report = parse_and_merge(coverage, None)?;
+
+    for file in files {
+        let key = vfs_key(&file.path);
+        let Some(file_coverage) = coverage_report.files.iter().find(|f| vfs_key(Path::new(&f.path)) == key) else {
+            continue;
+        };
+
+        let (warning_threshold, _) = config.complexity.thresholds_for(&file.path, Some(file.language));
+        let hotspots =
+            find_hotspots(file_coverage, &file.content, file.language, warning_threshold, hotspot_coverage_threshold)?;
+
+        if !hotspots.is_empty() {
+            println!("{}", report_hotspots(file_coverage, &hotspots));
+        }
     }
 
     Ok(())
 }
 
-fn print_complexity_report(report: &Report, config: &Config) {
+/// Normalize a path to the same canonical key `combined::build_reports` uses,
+/// so LCOV `SF:` paths and loader-discovered paths match regardless of `./`
+/// prefixes, separators, or relative anchor.
+fn vfs_key(path: &Path) -> String {
+    VfsPath::from(path).to_canonical_string()
+}
+
+/// Emit GitHub Actions workflow-command annotations (`::warning ...`/`::error
+/// ...`) for every function over `config.complexity`'s thresholds, so a CI
+/// job running this surfaces them as inline PR annotations the same way
+/// rustfmt/clippy problem matchers do.
+fn print_github_annotations(report: &Report, config: &Config) {
+    for file in &report.files {
+        let language = Language::from_path(&file.path).ok();
+        let (warning_threshold, error_threshold) = config.complexity.thresholds_for(&file.path, language);
+
+        for func in &file.cyclomatic.functions {
+            let level = if func.complexity > error_threshold {
+                "error"
+            } else if func.complexity > warning_threshold {
+                "warning"
+            } else {
+                continue;
+            };
+
+            let message = format!("{} has cyclomatic complexity {}", func.name, func.complexity);
+            println!(
+                "::{} file={},line={}::{}",
+                level,
+                escape_property(&file.path.display().to_string()),
+                func.line,
+                escape_data(&message)
+            );
+        }
+    }
+}
+
+/// Escape a workflow-command message per GitHub's encoding rules.
+fn escape_data(s: &str) -> String {
+    s.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+/// Escape a workflow-command property value (e.g. `file=...`), which also
+/// requires `:` and `,` to be escaped since they delimit properties.
+fn escape_property(s: &str) -> String {
+    escape_data(s).replace(':', "%3A").replace(',', "%2C")
+}
+
+fn print_complexity_report(report: &Report, config: &Config, fail_on: FailOn, breach_count: usize) {
     println!("{}", "=".repeat(80).cyan());
     println!("{}", "COMPLEXITY ANALYSIS".cyan().bold());
     println!("{}\n", "=".repeat(80).cyan());
@@ -78,12 +264,15 @@ fn print_complexity_report(report: &Report, config: &Config) {
     for file in &report.files {
         println!("{} {}", "FILE:".blue().bold(), file.path.display().bold());
 
+        let language = Language::from_path(&file.path).ok();
+        let (warning_threshold, error_threshold) = config.complexity.thresholds_for(&file.path, language);
+
         let complexity_value = file.cyclomatic.file_complexity;
         let complexity_text = format!("Cyclomatic Complexity:   {complexity_value}");
 
-        if complexity_value > config.complexity.error_threshold {
+        if complexity_value > error_threshold {
             println!("    {}", complexity_text.red().bold());
-        } else if complexity_value > config.complexity.warning_threshold {
+        } else if complexity_value > warning_threshold {
             println!("    {}", complexity_text.yellow());
         } else {
             println!("    {}", complexity_text.green());
@@ -101,9 +290,9 @@ fn print_complexity_report(report: &Report, config: &Config) {
                     func.name, func.line, func.complexity
                 );
 
-                if func.complexity > config.complexity.error_threshold {
+                if func.complexity > error_threshold {
                     println!("{}", func_text.red());
-                } else if func.complexity > config.complexity.warning_threshold {
+                } else if func.complexity > warning_threshold {
                     println!("{}", func_text.yellow());
                 } else {
                     println!("{func_text}");
@@ -113,5 +302,14 @@ fn print_complexity_report(report: &Report, config: &Config) {
         }
     }
 
+    if !matches!(fail_on, FailOn::Never) {
+        let footer = format!("Fail-on level: {} ({breach_count} breach(es))", fail_on.label());
+        if breach_count > 0 {
+            println!("{}\n", footer.red().bold());
+        } else {
+            println!("{}\n", footer.green());
+        }
+    }
+
     println!("{}", "=".repeat(80).cyan());
 }
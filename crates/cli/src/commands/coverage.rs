@@ -1,23 +1,34 @@
 use anyhow::Result;
-use mccabre_core::coverage::{FileCoverage, parse_coverage_from_file};
-use mccabre_core::reporter::{coverage_jsonl::JsonlReporter, coverage_term::report_coverage};
+use mccabre_core::coverage::{FileCoverage, parse_and_merge, parse_coverage_from_file, remap_through_source_maps};
+use mccabre_core::reporter::{
+    HtmlReporter, check_thresholds, coverage_jsonl::JsonlReporter, coverage_term::report_coverage,
+    report_directory_summary,
+};
 use owo_colors::OwoColorize;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-pub fn run(from: PathBuf, jsonl: Option<PathBuf>, repo_root: Option<PathBuf>) -> Result<()> {
-    if !from.exists() {
-        eprintln!("{}", format!("LCOV file not found: {}", from.display()).red());
-        std::process::exit(1);
+#[allow(clippy::too_many_arguments)]
+pub fn run(
+    from: Vec<PathBuf>, jsonl: Option<PathBuf>, html: Option<PathBuf>, repo_root: Option<PathBuf>, by_directory: bool,
+    fail_under: Option<f64>, fail_under_file: Option<f64>, source_maps: bool,
+) -> Result<()> {
+    for path in &from {
+        if !path.exists() {
+            eprintln!("{}", format!("LCOV file not found: {}", path.display()).red());
+            std::process::exit(1);
+        }
     }
 
-    let report = parse_coverage_from_file(&from, repo_root.as_deref())?;
+    let report = parse_and_merge(&from, repo_root.as_deref())?;
 
     if report.files.is_empty() {
         eprintln!("{}", "No coverage data found".yellow());
         return Ok(());
     }
 
+    let report = if source_maps { remap_through_source_maps(&report, repo_root.as_deref()) } else { report };
+
     if let Some(jsonl_path) = jsonl {
         let mut reporter = JsonlReporter::new();
         reporter.add_report(&report);
@@ -31,13 +42,34 @@ pub fn run(from: PathBuf, jsonl: Option<PathBuf>, repo_root: Option<PathBuf>) ->
         );
     }
 
-    println!("{}", report_coverage(&report));
+    if let Some(html_dir) = html {
+        let reporter = HtmlReporter::new(&report);
+        reporter.write_to_dir(&html_dir, repo_root.as_deref())?;
+
+        println!(
+            "{}",
+            format!("HTML report written to: {}", html_dir.display()).green().bold()
+        );
+    }
+
+    if by_directory {
+        println!("{}", report_directory_summary(&report));
+    } else {
+        println!("{}", report_coverage(&report));
+    }
+
+    if let Some(breaches) = check_thresholds(&report, fail_under, fail_under_file) {
+        eprintln!("{}", breaches);
+        std::process::exit(1);
+    }
 
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn run_show(
     from: PathBuf, repo_root: Option<PathBuf>, path: Option<PathBuf>, truncate_threshold: Option<usize>,
+    fail_under: Option<f64>, fail_under_file: Option<f64>,
 ) -> Result<()> {
     if !from.exists() {
         eprintln!("{}", format!("LCOV file not found: {}", from.display()).red());
@@ -67,6 +99,11 @@ pub fn run_show(
         }
     }
 
+    if let Some(breaches) = check_thresholds(&report, fail_under, fail_under_file) {
+        eprintln!("{}", breaches);
+        std::process::exit(1);
+    }
+
     Ok(())
 }
 
@@ -89,10 +126,12 @@ fn show_file_coverage(
 
             let source_code = fs::read_to_string(path)?;
             let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("txt");
+            let language = mccabre_core::tokenizer::Language::detect(path, &source_code)?;
 
             let output = mccabre_core::reporter::report_detailed_file_view(
                 file,
                 &source_code,
+                language,
                 extension,
                 truncate_threshold.unwrap_or(5),
             );
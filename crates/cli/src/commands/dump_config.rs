@@ -11,6 +11,7 @@ pub fn run(config_path: Option<PathBuf>, output_path: Option<PathBuf>) -> Result
         println!("{}", "Using default configuration".blue());
         Config::load_default()?
     };
+    let config = config.apply_env()?;
 
     println!();
     println!("{}", "CONFIGURATION".green().bold());
@@ -25,10 +26,24 @@ pub fn run(config_path: Option<PathBuf>, output_path: Option<PathBuf>) -> Result
     println!("{}", "Clone Detection Settings:".yellow().bold());
     println!("  Enabled:               {}", config.clones.enabled);
     println!("  Minimum tokens:        {}", config.clones.min_tokens);
+    println!("  Detection mode:        {}", config.clones.detection_mode);
     println!();
 
     println!("{}", "File Settings:".yellow().bold());
     println!("  Respect .gitignore:    {}", config.files.respect_gitignore);
+    println!("  Respect ignore file:   {}", config.files.respect_ignore_file);
+    if !config.files.include.is_empty() {
+        println!("  Include globs:         {}", config.files.include.join(", "));
+    }
+    if !config.files.exclude.is_empty() {
+        println!("  Exclude globs:         {}", config.files.exclude.join(", "));
+    }
+    if !config.files.languages.is_empty() {
+        println!("  Languages:             {}", config.files.languages.join(", "));
+    }
+    if !config.files.languages_exclude.is_empty() {
+        println!("  Excluded languages:    {}", config.files.languages_exclude.join(", "));
+    }
     println!();
 
     println!("{}", "=".repeat(80).cyan());
@@ -1,23 +1,49 @@
 use anyhow::Result;
 use mccabre_core::{
+    combined::{self, FileReport},
+    compare::Baseline,
     complexity::loc::{FileLocReport, LocMetrics, LocReport, RankBy},
     config::Config,
+    coverage::parse_and_merge,
     loader::FileLoader,
+    tokenizer::Language,
 };
 use owo_colors::OwoColorize;
 use std::path::PathBuf;
 
+#[allow(clippy::too_many_arguments)]
 pub fn run(
     path: PathBuf, json: bool, rank_by: RankBy, rank_dirs: bool, config_path: Option<PathBuf>, respect_gitignore: bool,
+    respect_ignore_file: bool, include: Vec<String>, exclude: Vec<String>, r#type: Vec<String>,
+    type_not: Vec<String>, threads: usize, save_baseline: Option<PathBuf>, coverage: Vec<PathBuf>,
 ) -> Result<()> {
     let config = if let Some(config_path) = config_path {
         Config::from_file(config_path)?
     } else {
-        Config::load_default()?
+        Config::load_for_path(&path)?
     };
-
-    let config = config.merge_with_cli(None, None, Some(respect_gitignore));
-    let loader = FileLoader::new().with_gitignore(config.files.respect_gitignore);
+    let config = config.apply_env()?;
+
+    let config = config.merge_with_cli(
+        None,
+        None,
+        Some(respect_gitignore),
+        Some(respect_ignore_file),
+        (!include.is_empty()).then_some(include),
+        (!exclude.is_empty()).then_some(exclude),
+        (!r#type.is_empty()).then_some(r#type),
+        (!type_not.is_empty()).then_some(type_not),
+        None,
+        None,
+    );
+    let loader = FileLoader::new()
+        .with_gitignore(config.files.respect_gitignore)
+        .with_ignore_file(config.files.respect_ignore_file)
+        .with_includes(&config.files.include)?
+        .with_excludes(&config.files.exclude)?
+        .with_languages(Language::parse_many(&config.files.languages)?)
+        .without_languages(Language::parse_many(&config.files.languages_exclude)?)
+        .with_threads(threads);
     let files = loader.load(&path)?;
 
     if files.is_empty() {
@@ -34,15 +60,81 @@ pub fn run(
 
     let report = LocReport::new(file_reports, rank_by, rank_dirs);
 
+    if let Some(baseline_path) = &save_baseline {
+        Baseline::new(Some(report.clone()), None).save(baseline_path)?;
+        println!(
+            "{}",
+            format!("Baseline saved to: {}", baseline_path.display())
+                .green()
+                .bold()
+        );
+    }
+
+    let combined_reports = if coverage.is_empty() {
+        None
+    } else {
+        let coverage_report = parse_and_merge(&coverage, None)?;
+        Some(combined::build_reports(
+            &files,
+            &report.files,
+            &coverage_report.files,
+            combined::RankBy::UncoveredLogical,
+        )?)
+    };
+
     if json {
         println!("{}", report.to_json()?);
+        if let Some(combined_reports) = &combined_reports {
+            println!("{}", combined::reports_to_json(combined_reports)?);
+        }
     } else {
         print_loc_report(&report, rank_by, rank_dirs);
+        if !report.duplicates.is_empty() {
+            print_duplicates_report(&report.duplicates);
+        }
+        if let Some(combined_reports) = &combined_reports {
+            print_uncovered_logical_report(combined_reports);
+        }
     }
 
     Ok(())
 }
 
+fn print_duplicates_report(duplicates: &[Vec<PathBuf>]) {
+    println!("{}", "DUPLICATES".green().bold());
+    println!("{}\n", "-".repeat(80).cyan());
+
+    for (idx, group) in duplicates.iter().enumerate() {
+        println!("{} {}", format!("Group #{}", idx + 1).yellow().bold(), "(byte-for-byte identical)".dimmed());
+        for path in group {
+            println!("  {} {}", "-".dimmed(), path.display());
+        }
+        println!();
+    }
+
+    println!("{}", "=".repeat(80).cyan());
+}
+
+fn print_uncovered_logical_report(reports: &[FileReport]) {
+    println!("{} {}", "FILES RANKED BY".green().bold(), "Uncovered Logical LOC".green().bold());
+    println!("{}\n", "-".repeat(80).cyan());
+
+    for (idx, file) in reports.iter().enumerate() {
+        if file.uncovered_logical_lines == 0 {
+            continue;
+        }
+
+        println!(
+            "{}. {} (Uncovered Logical LOC: {})",
+            (idx + 1).to_string().dimmed(),
+            file.path.display().bold(),
+            file.uncovered_logical_lines.to_string().yellow()
+        );
+    }
+
+    println!("{}", "=".repeat(80).cyan());
+}
+
 fn print_loc_report(report: &LocReport, rank_by: RankBy, rank_dirs: bool) {
     println!("{}", "=".repeat(80).cyan());
     println!("{}", "LINES OF CODE ANALYSIS".cyan().bold());
@@ -54,7 +146,8 @@ fn print_loc_report(report: &LocReport, rank_by: RankBy, rank_dirs: bool) {
     println!("Total physical LOC:          {}", report.summary.total_physical.bold());
     println!("Total logical LOC:           {}", report.summary.total_logical.bold());
     println!("Total comment lines:         {}", report.summary.total_comments.bold());
-    println!("Total blank lines:           {}\n", report.summary.total_blank.bold());
+    println!("Total blank lines:           {}", report.summary.total_blank.bold());
+    println!("Duplicate files:             {}\n", report.summary.duplicate_files.bold());
 
     let rank_label = match rank_by {
         RankBy::Logical => "Logical LOC",
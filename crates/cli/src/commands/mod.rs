@@ -0,0 +1,6 @@
+pub mod analyze;
+pub mod clones;
+pub mod compare;
+pub mod complexity;
+pub mod dump_config;
+pub mod loc;
@@ -1,17 +1,100 @@
+use crate::theme::ThemeStore;
+use anyhow::{Result, bail};
+use mccabre_core::cloner::rolling_hash::token_hash;
+use mccabre_core::tokenizer::{Language, TokenType, Tokenizer};
 use owo_colors::OwoColorize;
+use std::collections::HashMap;
+use std::env;
+use std::path::PathBuf;
 use syntect::easy::HighlightLines;
-use syntect::highlighting::{Color, Style, ThemeSet};
+use syntect::highlighting::{Color, FontStyle, Style, ThemeSet};
 use syntect::parsing::SyntaxSet;
 use syntect::util::LinesWithEndings;
 
+const DEFAULT_THEME: &str = "base16-ocean.dark";
+
+/// Fixed palette of visually-distinct colors assigned to identifiers by
+/// hashing their text, so the same name always gets the same color.
+const IDENTIFIER_PALETTE: [Color; 8] = [
+    Color { r: 230, g: 126, b: 34, a: 255 },
+    Color { r: 46, g: 204, b: 113, a: 255 },
+    Color { r: 52, g: 152, b: 219, a: 255 },
+    Color { r: 231, g: 76, b: 60, a: 255 },
+    Color { r: 241, g: 196, b: 15, a: 255 },
+    Color { r: 155, g: 89, b: 182, a: 255 },
+    Color { r: 26, g: 188, b: 156, a: 255 },
+    Color { r: 236, g: 112, b: 99, a: 255 },
+];
+
+/// Terminal color capability, from richest to most conservative
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorMode {
+    /// 24-bit SGR escapes straight from the syntect theme's RGB
+    TrueColor,
+    /// Quantized to the xterm 256-color palette
+    Ansi256,
+    /// The original dominant-channel heuristic, for terminals with only the
+    /// 16 named colors
+    Ansi16,
+}
+
+impl ColorMode {
+    /// Detect capability from `COLORTERM`/`TERM`, defaulting to `Ansi16` when
+    /// neither hints at richer support.
+    pub fn detect() -> Self {
+        if let Ok(colorterm) = env::var("COLORTERM") {
+            if colorterm == "truecolor" || colorterm == "24bit" {
+                return Self::TrueColor;
+            }
+        }
+
+        if let Ok(term) = env::var("TERM") {
+            if term.contains("256color") {
+                return Self::Ansi256;
+            }
+        }
+
+        Self::Ansi16
+    }
+}
+
 pub struct Highlighter {
     syntax_set: SyntaxSet,
     theme_set: ThemeSet,
+    theme_name: String,
+    color_mode: ColorMode,
 }
 
 impl Highlighter {
     pub fn new() -> Self {
-        Self { syntax_set: SyntaxSet::load_defaults_newlines(), theme_set: ThemeSet::load_defaults() }
+        Self {
+            syntax_set: SyntaxSet::load_defaults_newlines(),
+            theme_set: ThemeSet::load_defaults(),
+            theme_name: DEFAULT_THEME.to_string(),
+            color_mode: ColorMode::detect(),
+        }
+    }
+
+    /// Build a highlighter using `name`, merging in any user-supplied
+    /// `.sublime-syntax`/`.tmTheme` files from the user config directory.
+    /// Errors with the list of available themes if `name` isn't found.
+    pub fn with_theme(name: &str) -> Result<Self> {
+        let config_dir = ThemeStore::default_dir().unwrap_or_else(|| PathBuf::from(".mccabre"));
+        let (syntax_set, theme_set) = ThemeStore::new(config_dir).load()?;
+
+        if !theme_set.themes.contains_key(name) {
+            let mut available: Vec<&str> = theme_set.themes.keys().map(String::as_str).collect();
+            available.sort_unstable();
+            bail!("unknown theme '{name}'; available themes: {}", available.join(", "));
+        }
+
+        Ok(Self { syntax_set, theme_set, theme_name: name.to_string(), color_mode: ColorMode::detect() })
+    }
+
+    /// Override the detected terminal color capability
+    pub fn with_color_mode(mut self, mode: ColorMode) -> Self {
+        self.color_mode = mode;
+        self
     }
 
     /// Highlight code with syntax highlighting
@@ -21,7 +104,7 @@ impl Highlighter {
             .find_syntax_by_extension(file_extension)
             .unwrap_or_else(|| self.syntax_set.find_syntax_plain_text());
 
-        let theme = &self.theme_set.themes["base16-ocean.dark"];
+        let theme = &self.theme_set.themes[&self.theme_name];
 
         let mut highlighter = HighlightLines::new(syntax, theme);
         let mut output = String::new();
@@ -30,12 +113,51 @@ impl Highlighter {
             let ranges = highlighter.highlight_line(line, &self.syntax_set).unwrap_or_default();
 
             for (style, text) in ranges {
-                output.push_str(&style_to_owo(&style, text));
+                output.push_str(&style_to_ansi(&style, text, self.color_mode));
             }
         }
 
         output
     }
+
+    /// Highlight code as [`Highlighter::highlight`] does, but with each
+    /// distinct identifier tinted a deterministic color on top of the
+    /// syntect theme styling. Two occurrences of the same clone with
+    /// renamed variables (a Type-2 clone) then show up as matching color
+    /// pairs between occurrences, the same way rust-analyzer's binding-hash
+    /// colors keep a local distinguishable across a function body.
+    pub fn highlight_with_identifier_colors(&self, code: &str, file_extension: &str, language: Language) -> Result<String> {
+        let identifier_colors = identifier_palette(code, language)?;
+
+        let syntax = self
+            .syntax_set
+            .find_syntax_by_extension(file_extension)
+            .unwrap_or_else(|| self.syntax_set.find_syntax_plain_text());
+
+        let theme = &self.theme_set.themes[&self.theme_name];
+
+        let mut highlighter = HighlightLines::new(syntax, theme);
+        let mut output = String::new();
+
+        for line in LinesWithEndings::from(code) {
+            let ranges = highlighter.highlight_line(line, &self.syntax_set).unwrap_or_default();
+
+            for (style, text) in ranges {
+                for (word, is_identifier) in split_words(text) {
+                    if is_identifier {
+                        if let Some(&color) = identifier_colors.get(word) {
+                            let overridden = Style { foreground: color, ..style };
+                            output.push_str(&style_to_ansi(&overridden, word, self.color_mode));
+                            continue;
+                        }
+                    }
+                    output.push_str(&style_to_ansi(&style, word, self.color_mode));
+                }
+            }
+        }
+
+        Ok(output)
+    }
 }
 
 impl Default for Highlighter {
@@ -44,6 +166,83 @@ impl Default for Highlighter {
     }
 }
 
+/// Hash every distinct identifier in `code` into a fixed palette entry
+fn identifier_palette(code: &str, language: Language) -> Result<HashMap<&str, Color>> {
+    let tokens = Tokenizer::new(code, language).tokenize()?;
+
+    let colors = tokens
+        .into_iter()
+        .filter_map(|token| match token.token_type {
+            TokenType::Identifier(name) => {
+                let color = IDENTIFIER_PALETTE[token_hash(name) as usize % IDENTIFIER_PALETTE.len()];
+                Some((name, color))
+            }
+            _ => None,
+        })
+        .collect();
+
+    Ok(colors)
+}
+
+/// Split `text` into maximal runs of identifier characters (`[A-Za-z0-9_]`)
+/// and everything else, tagging each run as identifier or not
+fn split_words(text: &str) -> Vec<(&str, bool)> {
+    let mut words = Vec::new();
+    let mut start = 0;
+    let mut in_word = false;
+
+    for (idx, ch) in text.char_indices() {
+        let is_word_char = ch.is_alphanumeric() || ch == '_';
+        if idx > start && is_word_char != in_word {
+            words.push((&text[start..idx], in_word));
+            start = idx;
+        }
+        in_word = is_word_char;
+    }
+
+    if start < text.len() {
+        words.push((&text[start..], in_word));
+    }
+
+    words
+}
+
+/// Render a syntect style as text colored per `mode`
+fn style_to_ansi(style: &Style, text: &str, mode: ColorMode) -> String {
+    match mode {
+        ColorMode::TrueColor => style_to_truecolor(style, text),
+        ColorMode::Ansi256 => style_to_ansi256(style, text),
+        ColorMode::Ansi16 => style_to_owo(style, text),
+    }
+}
+
+/// Emit a 24-bit SGR escape directly from the theme's RGB foreground
+fn style_to_truecolor(style: &Style, text: &str) -> String {
+    let fg = style.foreground;
+    let bold = if style.font_style.contains(FontStyle::BOLD) { "1;" } else { "" };
+    format!("\x1b[{bold}38;2;{};{};{}m{text}\x1b[0m", fg.r, fg.g, fg.b)
+}
+
+/// Quantize the theme's RGB foreground to the nearest xterm 256-color index
+fn style_to_ansi256(style: &Style, text: &str) -> String {
+    let index = rgb_to_xterm256(style.foreground);
+    let bold = if style.font_style.contains(FontStyle::BOLD) { "1;" } else { "" };
+    format!("\x1b[{bold}38;5;{index}m{text}\x1b[0m")
+}
+
+/// Map an RGB color to the xterm 256-color cube (16..=231) or, for
+/// near-grayscale colors, the 24-step grayscale ramp (232..=255)
+fn rgb_to_xterm256(color: Color) -> u8 {
+    if is_grayscale(color) {
+        let avg = (color.r as u16 + color.g as u16 + color.b as u16) / 3;
+        let step = ((avg as f64 / 255.0) * 23.0).round() as u8;
+        232 + step
+    } else {
+        let cube_index = |c: u8| ((c as f64 / 51.0).round() as u8).min(5);
+        16 + 36 * cube_index(color.r) + 6 * cube_index(color.g) + cube_index(color.b)
+    }
+}
+
 /// Convert syntect Style to owo-colors styled text
 fn style_to_owo(style: &Style, text: &str) -> String {
     let fg = style.foreground;
@@ -232,4 +431,81 @@ mod tests {
         let styled = style_to_owo(&style, text);
         assert!(styled.contains(text));
     }
+
+    #[test]
+    fn test_style_to_truecolor_emits_24bit_escape() {
+        let style = Style {
+            foreground: Color { r: 10, g: 20, b: 30, a: 255 },
+            background: Color { r: 0, g: 0, b: 0, a: 255 },
+            font_style: syntect::highlighting::FontStyle::empty(),
+        };
+        let styled = style_to_truecolor(&style, "x");
+        assert_eq!(styled, "\x1b[38;2;10;20;30mx\x1b[0m");
+    }
+
+    #[test]
+    fn test_rgb_to_xterm256_color_cube() {
+        let color = Color { r: 255, g: 0, b: 0, a: 255 };
+        assert_eq!(rgb_to_xterm256(color), 16 + 36 * 5);
+    }
+
+    #[test]
+    fn test_rgb_to_xterm256_grayscale_ramp() {
+        let color = Color { r: 128, g: 128, b: 128, a: 255 };
+        let index = rgb_to_xterm256(color);
+        assert!((232..=255).contains(&index));
+    }
+
+    #[test]
+    fn test_identifier_palette_is_stable_across_calls() {
+        let code = "let x = 1;\nlet y = x + 1;";
+        let first = identifier_palette(code, Language::Rust).unwrap();
+        let second = identifier_palette(code, Language::Rust).unwrap();
+
+        assert_eq!(first.get("x"), second.get("x"));
+        assert_eq!(first.get("y"), second.get("y"));
+    }
+
+    #[test]
+    fn test_identifier_palette_distinguishes_names() {
+        let code = "let alpha = 1;\nlet beta = 2;";
+        let colors = identifier_palette(code, Language::Rust).unwrap();
+
+        assert!(colors.contains_key("alpha"));
+        assert!(colors.contains_key("beta"));
+    }
+
+    #[test]
+    fn test_split_words_separates_identifiers_from_punctuation() {
+        let words = split_words("let x_1 = foo();");
+
+        assert_eq!(
+            words,
+            vec![
+                ("let", true),
+                (" ", false),
+                ("x_1", true),
+                (" ", false),
+                ("=", false),
+                (" ", false),
+                ("foo", true),
+                ("();", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_words_empty_string() {
+        assert!(split_words("").is_empty());
+    }
+
+    #[test]
+    fn test_highlight_with_identifier_colors_preserves_text() {
+        let highlighter = Highlighter::new();
+        let code = "let x = 1;\nlet y = x + 1;";
+        let highlighted = highlighter.highlight_with_identifier_colors(code, "rs", Language::Rust).unwrap();
+
+        assert!(highlighted.contains('x'));
+        assert!(highlighted.contains('y'));
+    }
 }
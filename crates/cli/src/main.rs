@@ -1,5 +1,6 @@
 mod commands;
 mod highlight;
+mod theme;
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
@@ -42,9 +43,55 @@ enum Commands {
         #[arg(long)]
         no_gitignore: bool,
 
+        /// Disable both gitignore and `.ignore`/`.mccabreignore` awareness
+        #[arg(long)]
+        no_ignore: bool,
+
         /// Disable syntax highlighting for clone code blocks
         #[arg(long)]
         no_highlight: bool,
+
+        /// Syntax highlighting theme name
+        #[arg(long)]
+        theme: Option<String>,
+
+        /// Only analyze paths matching this glob (repeatable), e.g. `src/**/*.rs`
+        #[arg(long)]
+        include: Vec<String>,
+
+        /// Exclude paths matching this glob (repeatable), e.g. `**/tests/**`
+        #[arg(long)]
+        exclude: Vec<String>,
+
+        /// Only analyze this language (repeatable), e.g. `rust`
+        #[arg(long = "type")]
+        r#type: Vec<String>,
+
+        /// Exclude this language from analysis (repeatable), e.g. `javascript`
+        #[arg(long = "type-not")]
+        type_not: Vec<String>,
+
+        /// Number of threads to walk directories with (0 = auto)
+        #[arg(long, default_value = "0")]
+        threads: usize,
+
+        /// Only analyze files changed relative to this git ref (e.g. `main`, `HEAD~3`)
+        #[arg(long, value_name = "REF", conflicts_with = "staged")]
+        since: Option<String>,
+
+        /// Only analyze files staged in the git index
+        #[arg(long, conflicts_with = "since")]
+        staged: bool,
+
+        /// Clone matching strictness: `exact` (copy-paste) or `normalized`
+        /// (identifier-renamed clones also match)
+        #[arg(long)]
+        detection_mode: Option<String>,
+
+        /// Winnowing window, in k-grams, fingerprints are sampled from
+        /// (default: 1, i.e. every k-gram)
+        #[arg(long)]
+        winnow_window: Option<usize>,
     },
 
     /// Analyze cyclomatic complexity and LOC only
@@ -53,9 +100,11 @@ enum Commands {
         #[arg(value_name = "PATH", default_value = ".")]
         path: PathBuf,
 
-        /// Output in JSON format
-        #[arg(short, long)]
-        json: bool,
+        /// Output format: `text` (colored report), `json`, `github`
+        /// (workflow-command annotations for inline PR review), or `sarif`
+        /// (code-scanning ingestion)
+        #[arg(long, value_enum, default_value = "text")]
+        format: commands::complexity::OutputFormat,
 
         /// Complexity threshold for warnings
         #[arg(long)]
@@ -68,6 +117,53 @@ enum Commands {
         /// Disable gitignore awareness
         #[arg(long)]
         no_gitignore: bool,
+
+        /// Disable both gitignore and `.ignore`/`.mccabreignore` awareness
+        #[arg(long)]
+        no_ignore: bool,
+
+        /// Only analyze paths matching this glob (repeatable), e.g. `src/**/*.rs`
+        #[arg(long)]
+        include: Vec<String>,
+
+        /// Exclude paths matching this glob (repeatable), e.g. `**/tests/**`
+        #[arg(long)]
+        exclude: Vec<String>,
+
+        /// Only analyze this language (repeatable), e.g. `rust`
+        #[arg(long = "type")]
+        r#type: Vec<String>,
+
+        /// Exclude this language from analysis (repeatable), e.g. `javascript`
+        #[arg(long = "type-not")]
+        type_not: Vec<String>,
+
+        /// Number of threads to walk directories with (0 = auto)
+        #[arg(long, default_value = "0")]
+        threads: usize,
+
+        /// Only analyze files changed relative to this git ref (e.g. `main`, `HEAD~3`)
+        #[arg(long, value_name = "REF", conflicts_with = "staged")]
+        since: Option<String>,
+
+        /// Only analyze files staged in the git index
+        #[arg(long, conflicts_with = "since")]
+        staged: bool,
+
+        /// Exit non-zero when a function reaches this complexity level or above
+        #[arg(long, value_enum, default_value = "never")]
+        fail_on: commands::complexity::FailOn,
+
+        /// LCOV file(s) to correlate against complexity; when given, prints
+        /// a worst-first table of high-complexity, low-coverage functions
+        /// (repeatable)
+        #[arg(long, value_name = "LCOV")]
+        coverage: Vec<PathBuf>,
+
+        /// Coverage rate (percent) below which a high-complexity function is
+        /// flagged as a hotspot
+        #[arg(long, default_value = "80.0")]
+        hotspot_coverage_threshold: f64,
     },
 
     /// Detect code clones only
@@ -92,9 +188,64 @@ enum Commands {
         #[arg(long)]
         no_gitignore: bool,
 
+        /// Disable both gitignore and `.ignore`/`.mccabreignore` awareness
+        #[arg(long)]
+        no_ignore: bool,
+
         /// Disable syntax highlighting for clone code blocks
         #[arg(long)]
         no_highlight: bool,
+
+        /// Syntax highlighting theme name
+        #[arg(long)]
+        theme: Option<String>,
+
+        /// Lines of context to show before/after each clone occurrence
+        #[arg(long, default_value = "0")]
+        context: usize,
+
+        /// Tint each identifier a deterministic color, so renamed-variable
+        /// clones line up as matching color pairs between occurrences
+        #[arg(long)]
+        highlight_renames: bool,
+
+        /// Only analyze paths matching this glob (repeatable), e.g. `src/**/*.rs`
+        #[arg(long)]
+        include: Vec<String>,
+
+        /// Exclude paths matching this glob (repeatable), e.g. `**/tests/**`
+        #[arg(long)]
+        exclude: Vec<String>,
+
+        /// Only analyze this language (repeatable), e.g. `rust`
+        #[arg(long = "type")]
+        r#type: Vec<String>,
+
+        /// Exclude this language from analysis (repeatable), e.g. `javascript`
+        #[arg(long = "type-not")]
+        type_not: Vec<String>,
+
+        /// Number of threads to walk directories with (0 = auto)
+        #[arg(long, default_value = "0")]
+        threads: usize,
+
+        /// Only analyze files changed relative to this git ref (e.g. `main`, `HEAD~3`)
+        #[arg(long, value_name = "REF", conflicts_with = "staged")]
+        since: Option<String>,
+
+        /// Only analyze files staged in the git index
+        #[arg(long, conflicts_with = "since")]
+        staged: bool,
+
+        /// Clone matching strictness: `exact` (copy-paste) or `normalized`
+        /// (identifier-renamed clones also match)
+        #[arg(long)]
+        detection_mode: Option<String>,
+
+        /// Winnowing window, in k-grams, fingerprints are sampled from
+        /// (default: 1, i.e. every k-gram)
+        #[arg(long)]
+        winnow_window: Option<usize>,
     },
 
     /// Display current configuration
@@ -133,6 +284,61 @@ enum Commands {
         /// Disable gitignore awareness
         #[arg(long)]
         no_gitignore: bool,
+
+        /// Disable both gitignore and `.ignore`/`.mccabreignore` awareness
+        #[arg(long)]
+        no_ignore: bool,
+
+        /// Only analyze paths matching this glob (repeatable), e.g. `src/**/*.rs`
+        #[arg(long)]
+        include: Vec<String>,
+
+        /// Exclude paths matching this glob (repeatable), e.g. `**/tests/**`
+        #[arg(long)]
+        exclude: Vec<String>,
+
+        /// Only analyze this language (repeatable), e.g. `rust`
+        #[arg(long = "type")]
+        r#type: Vec<String>,
+
+        /// Exclude this language from analysis (repeatable), e.g. `javascript`
+        #[arg(long = "type-not")]
+        type_not: Vec<String>,
+
+        /// Number of threads to walk directories with (0 = auto)
+        #[arg(long, default_value = "0")]
+        threads: usize,
+
+        /// Save this run's LOC results as a baseline snapshot, for later use
+        /// with `mccabre compare`
+        #[arg(long, value_name = "PATH")]
+        save_baseline: Option<PathBuf>,
+
+        /// LCOV file(s) to join against LOC data; when given, also ranks
+        /// files by uncovered logical lines (repeatable)
+        #[arg(long, value_name = "LCOV")]
+        coverage: Vec<PathBuf>,
+    },
+
+    /// Diff two saved baseline snapshots and fail on regressions
+    Compare {
+        /// Path to the earlier baseline snapshot (e.g. from `--save-baseline` on `main`)
+        baseline: PathBuf,
+
+        /// Path to the later baseline snapshot to compare against it
+        current: PathBuf,
+
+        /// Fail if total coverage rate drops by more than this many percentage points
+        #[arg(long)]
+        max_coverage_drop: Option<f64>,
+
+        /// Fail if any single file's uncovered-line count increases at all
+        #[arg(long)]
+        fail_on_new_misses: bool,
+
+        /// Output in JSON format
+        #[arg(short, long)]
+        json: bool,
     },
 }
 
@@ -140,25 +346,138 @@ fn main() -> Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Analyze { path, json, threshold, min_tokens, config, no_gitignore, no_highlight } => {
-            commands::analyze::run(
-                path,
-                json,
-                threshold,
-                Some(min_tokens),
-                config,
-                !no_gitignore,
-                !no_highlight,
-            )
-        }
-        Commands::Complexity { path, json, threshold, config, no_gitignore } => {
-            commands::complexity::run(path, json, threshold, config, !no_gitignore)
-        }
-        Commands::Clones { path, json, min_tokens, config, no_gitignore, no_highlight } => {
-            commands::clones::run(path, json, Some(min_tokens), config, !no_gitignore, !no_highlight)
-        }
+        Commands::Analyze {
+            path,
+            json,
+            threshold,
+            min_tokens,
+            config,
+            no_gitignore,
+            no_ignore,
+            no_highlight,
+            theme,
+            include,
+            exclude,
+            r#type,
+            type_not,
+            threads,
+            since,
+            staged,
+            detection_mode,
+            winnow_window,
+        } => commands::analyze::run(
+            path,
+            json,
+            threshold,
+            Some(min_tokens),
+            config,
+            !no_gitignore && !no_ignore,
+            !no_ignore,
+            !no_highlight,
+            theme,
+            include,
+            exclude,
+            r#type,
+            type_not,
+            threads,
+            since,
+            staged,
+            detection_mode,
+            winnow_window,
+        ),
+        Commands::Complexity {
+            path,
+            format,
+            threshold,
+            config,
+            no_gitignore,
+            no_ignore,
+            include,
+            exclude,
+            r#type,
+            type_not,
+            threads,
+            since,
+            staged,
+            fail_on,
+            coverage,
+            hotspot_coverage_threshold,
+        } => commands::complexity::run(
+            path,
+            format,
+            threshold,
+            config,
+            !no_gitignore && !no_ignore,
+            !no_ignore,
+            include,
+            exclude,
+            r#type,
+            type_not,
+            threads,
+            since,
+            staged,
+            fail_on,
+            coverage,
+            hotspot_coverage_threshold,
+        ),
+        Commands::Clones {
+            path,
+            json,
+            min_tokens,
+            config,
+            no_gitignore,
+            no_ignore,
+            no_highlight,
+            theme,
+            context,
+            highlight_renames,
+            include,
+            exclude,
+            r#type,
+            type_not,
+            threads,
+            since,
+            staged,
+            detection_mode,
+            winnow_window,
+        } => commands::clones::run(
+            path,
+            json,
+            Some(min_tokens),
+            config,
+            !no_gitignore && !no_ignore,
+            !no_ignore,
+            !no_highlight,
+            theme,
+            context,
+            highlight_renames,
+            include,
+            exclude,
+            r#type,
+            type_not,
+            threads,
+            since,
+            staged,
+            detection_mode,
+            winnow_window,
+        ),
         Commands::DumpConfig { config, output } => commands::dump_config::run(config, output),
-        Commands::Loc { path, json, rank_by, rank_dirs, config, no_gitignore } => {
+        Commands::Loc {
+            path,
+            json,
+            rank_by,
+            rank_dirs,
+            config,
+            no_gitignore,
+            no_ignore,
+            include,
+            exclude,
+            r#type,
+            type_not,
+            threads,
+            save_baseline,
+            coverage,
+        } => {
             use mccabre_core::complexity::loc::RankBy;
 
             let rank_by = match rank_by.to_lowercase().as_str() {
@@ -172,7 +491,25 @@ fn main() -> Result<()> {
                 }
             };
 
-            commands::loc::run(path, json, rank_by, rank_dirs, config, !no_gitignore)
+            commands::loc::run(
+                path,
+                json,
+                rank_by,
+                rank_dirs,
+                config,
+                !no_gitignore && !no_ignore,
+                !no_ignore,
+                include,
+                exclude,
+                r#type,
+                type_not,
+                threads,
+                save_baseline,
+                coverage,
+            )
+        }
+        Commands::Compare { baseline, current, max_coverage_drop, fail_on_new_misses, json } => {
+            commands::compare::run(baseline, current, max_coverage_drop, fail_on_new_misses, json)
         }
     }
 }
@@ -0,0 +1,157 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use syntect::highlighting::ThemeSet;
+use syntect::parsing::SyntaxSet;
+
+const DUMP_FILE_NAME: &str = "assets.dump.gz";
+const SYNTAX_DIR_NAME: &str = "syntaxes";
+const THEME_DIR_NAME: &str = "themes";
+
+/// Loads and caches the merged set of syntect's bundled syntaxes/themes plus
+/// any user-supplied `.sublime-syntax`/`.tmTheme` files.
+///
+/// Building the merged `SyntaxSet`/`ThemeSet` from scratch is slow enough to
+/// notice on every CLI invocation, so the result is cached as a
+/// gzip-compressed bincode dump next to the user's source files and only
+/// rebuilt when a source file is newer than the dump.
+pub struct ThemeStore {
+    config_dir: PathBuf,
+}
+
+impl ThemeStore {
+    pub fn new(config_dir: PathBuf) -> Self {
+        Self { config_dir }
+    }
+
+    /// The default per-user config directory, or `None` if the platform
+    /// doesn't expose one.
+    pub fn default_dir() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("mccabre"))
+    }
+
+    /// Load the merged syntax/theme sets, rebuilding the cached dump only if
+    /// it's missing, unreadable, or stale.
+    pub fn load(&self) -> Result<(SyntaxSet, ThemeSet)> {
+        if self.dump_is_fresh() {
+            if let Ok(assets) = self.read_dump() {
+                return Ok(assets);
+            }
+        }
+
+        let assets = self.build()?;
+        // A failure to cache shouldn't fail the whole load; we just rebuild
+        // again next time.
+        let _ = self.write_dump(&assets);
+        Ok(assets)
+    }
+
+    fn build(&self) -> Result<(SyntaxSet, ThemeSet)> {
+        let mut syntax_builder = SyntaxSet::load_defaults_newlines().into_builder();
+        let syntax_dir = self.syntax_dir();
+        if syntax_dir.is_dir() {
+            syntax_builder
+                .add_from_folder(&syntax_dir, true)
+                .with_context(|| format!("loading custom syntaxes from {}", syntax_dir.display()))?;
+        }
+
+        let mut theme_set = ThemeSet::load_defaults();
+        let theme_dir = self.theme_dir();
+        if theme_dir.is_dir() {
+            for entry in fs::read_dir(&theme_dir)
+                .with_context(|| format!("reading theme directory {}", theme_dir.display()))?
+            {
+                let path = entry?.path();
+                if path.extension().is_some_and(|ext| ext == "tmTheme") {
+                    let name = path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+                    let theme = ThemeSet::get_theme(&path)
+                        .with_context(|| format!("loading theme {}", path.display()))?;
+                    theme_set.themes.insert(name, theme);
+                }
+            }
+        }
+
+        Ok((syntax_builder.build(), theme_set))
+    }
+
+    fn dump_is_fresh(&self) -> bool {
+        let Ok(dump_meta) = fs::metadata(self.dump_path()) else { return false };
+        let Ok(dump_time) = dump_meta.modified() else { return false };
+
+        [self.syntax_dir(), self.theme_dir()].iter().all(|dir| match Self::newest_mtime(dir) {
+            Some(newest) => newest <= dump_time,
+            None => true,
+        })
+    }
+
+    fn newest_mtime(dir: &Path) -> Option<SystemTime> {
+        fs::read_dir(dir).ok()?.filter_map(|entry| entry.ok()?.metadata().ok()?.modified().ok()).max()
+    }
+
+    fn read_dump(&self) -> Result<(SyntaxSet, ThemeSet)> {
+        let file = fs::File::open(self.dump_path())?;
+        let mut decoder = flate2::read::GzDecoder::new(file);
+        let assets = bincode::deserialize_from(&mut decoder)?;
+        Ok(assets)
+    }
+
+    fn write_dump(&self, assets: &(SyntaxSet, ThemeSet)) -> Result<()> {
+        fs::create_dir_all(&self.config_dir)?;
+        let file = fs::File::create(self.dump_path())?;
+        let mut encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+        bincode::serialize_into(&mut encoder, assets)?;
+        encoder.finish()?;
+        Ok(())
+    }
+
+    fn dump_path(&self) -> PathBuf {
+        self.config_dir.join(DUMP_FILE_NAME)
+    }
+
+    fn syntax_dir(&self) -> PathBuf {
+        self.config_dir.join(SYNTAX_DIR_NAME)
+    }
+
+    fn theme_dir(&self) -> PathBuf {
+        self.config_dir.join(THEME_DIR_NAME)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_without_custom_assets_falls_back_to_defaults() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = ThemeStore::new(temp_dir.path().to_path_buf());
+
+        let (syntax_set, theme_set) = store.load().unwrap();
+        assert!(!syntax_set.syntaxes().is_empty());
+        assert!(theme_set.themes.contains_key("base16-ocean.dark"));
+    }
+
+    #[test]
+    fn test_load_caches_dump_to_disk() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = ThemeStore::new(temp_dir.path().to_path_buf());
+
+        store.load().unwrap();
+        assert!(store.dump_path().exists());
+        assert!(store.dump_is_fresh());
+    }
+
+    #[test]
+    fn test_dump_is_stale_after_custom_syntax_added() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = ThemeStore::new(temp_dir.path().to_path_buf());
+        store.load().unwrap();
+
+        fs::create_dir_all(store.syntax_dir()).unwrap();
+        fs::write(store.syntax_dir().join("extra.sublime-syntax"), "name: Extra\n").unwrap();
+
+        assert!(!store.dump_is_fresh());
+    }
+}
@@ -0,0 +1,49 @@
+//! Benchmark for the byte-cursor tokenizer against a multi-megabyte source,
+//! demonstrating that tokenizing no longer pays an O(n) `Vec<char>`/`String`
+//! allocation per token.
+//!
+//! Wiring this in requires a `[dev-dependencies] criterion = "0.5"` and a
+//! `[[bench]] name = "tokenizer_bench" harness = false` entry in this crate's
+//! `Cargo.toml`, which doesn't exist in this tree (see the workspace's other
+//! source-only snapshots); run with `cargo bench --bench tokenizer_bench`
+//! once one is added.
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use mccabre_core::tokenizer::{Language, Tokenizer};
+
+/// Build a multi-megabyte synthetic Rust source by repeating a small function
+/// template, exercising identifiers, operators, literals, and comments.
+fn large_source(target_bytes: usize) -> String {
+    const TEMPLATE: &str = r#"
+fn process_item(index: usize, value: i64) -> i64 {
+    // accumulate while the running total stays positive
+    let mut total = value;
+    while total > 0 && index % 2 == 0 {
+        total -= 1;
+    }
+    match index {
+        0 => total,
+        _ => total + index as i64,
+    }
+}
+"#;
+
+    let mut source = String::with_capacity(target_bytes + TEMPLATE.len());
+    while source.len() < target_bytes {
+        source.push_str(TEMPLATE);
+    }
+    source
+}
+
+fn bench_tokenize(c: &mut Criterion) {
+    let source = large_source(4 * 1024 * 1024);
+
+    c.bench_function("tokenize_4mb_rust_source", |b| {
+        b.iter(|| {
+            let tokens = Tokenizer::new(black_box(&source), Language::Rust).tokenize().unwrap();
+            black_box(tokens.len())
+        })
+    });
+}
+
+criterion_group!(benches, bench_tokenize);
+criterion_main!(benches);
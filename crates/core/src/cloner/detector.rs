@@ -1,6 +1,7 @@
 use crate::Result;
-use crate::cloner::rolling_hash::{RollingHash, token_hash};
-use crate::tokenizer::{Language, Token, Tokenizer};
+use crate::cloner::rolling_hash::token_hash;
+use crate::cloner::winnow::winnow;
+use crate::tokenizer::{Language, Token, TokenType, Tokenizer};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -30,66 +31,108 @@ pub struct CloneLocation {
     pub end_line: usize,
 }
 
+/// How strictly token sequences must match to count as the same clone
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DetectionMode {
+    /// Token text must match exactly (copy-paste clones)
+    #[default]
+    Exact,
+    /// Identifier tokens are normalized to a single placeholder, so clones
+    /// that only differ in variable/parameter names are still detected
+    Normalized,
+}
+
+impl DetectionMode {
+    /// Parse a user-facing name (e.g. a CLI flag or config value), case-insensitively
+    pub fn parse_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "exact" => Some(Self::Exact),
+            "normalized" => Some(Self::Normalized),
+            _ => None,
+        }
+    }
+}
+
+/// Placeholder text hashed in place of an identifier's own text under
+/// [`DetectionMode::Normalized`]. The leading/trailing NULs keep it
+/// unreachable by any real identifier the tokenizer could produce.
+const NORMALIZED_IDENTIFIER_PLACEHOLDER: &str = "\0identifier\0";
+
 pub struct CloneDetector {
     /// Minimum number of tokens to consider as a clone
     _min_tokens: usize,
     /// Window size for rolling hash
     window_size: usize,
+    /// Exact vs. identifier-normalized matching
+    mode: DetectionMode,
+    /// Winnowing window (in k-grams) fingerprints are sampled from; `1`
+    /// samples every k-gram (no subsampling, the default), while widening it
+    /// trades exhaustive candidate coverage for fewer, sparser fingerprints
+    winnow_window: usize,
 }
 
 impl Default for CloneDetector {
     fn default() -> Self {
-        Self { _min_tokens: 30, window_size: 30 }
+        Self { _min_tokens: 30, window_size: 30, mode: DetectionMode::default(), winnow_window: 1 }
     }
 }
 
 impl CloneDetector {
     pub fn new(min_tokens: usize) -> Self {
-        Self { _min_tokens: min_tokens, window_size: min_tokens }
+        Self { _min_tokens: min_tokens, window_size: min_tokens, mode: DetectionMode::default(), winnow_window: 1 }
+    }
+
+    /// Use `mode` for matching, e.g. [`DetectionMode::Normalized`] to catch
+    /// clones that only differ in identifier names
+    pub fn with_mode(mut self, mode: DetectionMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sample candidate fingerprints from every `winnow_window` consecutive
+    /// k-grams (see [`crate::cloner::winnow::winnow`]) instead of every
+    /// single k-gram, trading exhaustive matching for speed on large trees
+    pub fn with_winnow_window(mut self, winnow_window: usize) -> Self {
+        self.winnow_window = winnow_window.max(1);
+        self
     }
 
     /// Detect clones in a single file
     pub fn detect_in_file(&self, source: &str, language: Language, file_path: PathBuf) -> Result<Vec<Clone>> {
         let tokens = Tokenizer::new(source, language).tokenize()?;
-        let significant_tokens: Vec<&Token> = tokens.iter().filter(|t| t.token_type.is_significant()).collect();
+        let significant_tokens: Vec<&Token<'_>> = tokens.iter().filter(|t| t.token_type.is_significant()).collect();
 
         if significant_tokens.len() < self.window_size {
             return Ok(Vec::new());
         }
 
-        let mut hash_map: HashMap<u64, Vec<(usize, usize)>> = HashMap::new();
-        let mut rh = RollingHash::new(self.window_size);
-
-        let token_hashes: Vec<u64> = significant_tokens.iter().map(|t| token_hash(&t.text)).collect();
+        let token_hashes: Vec<u64> = significant_tokens.iter().map(|t| token_signature(t, self.mode)).collect();
+        let mut hash_map: HashMap<u64, Vec<usize>> = HashMap::new();
 
-        rh.init(&token_hashes[0..self.window_size]);
-        let start_line = significant_tokens[0].line;
-        let end_line = significant_tokens[self.window_size - 1].line;
-        hash_map.entry(rh.get()).or_default().push((start_line, end_line));
-
-        for i in self.window_size..token_hashes.len() {
-            let hash = rh.roll(token_hashes[i - self.window_size], token_hashes[i]);
-            let start_line = significant_tokens[i - self.window_size + 1].line;
-            let end_line = significant_tokens[i].line;
-            hash_map.entry(hash).or_default().push((start_line, end_line));
+        for (hash, start) in winnow(&token_hashes, self.window_size, self.winnow_window) {
+            hash_map.entry(hash).or_default().push(start);
         }
 
+        let spans = merge_maximal_spans(&token_hashes, hash_map, self.window_size);
+
         let mut clones = Vec::new();
         let mut clone_id = 0;
 
-        for (hash, locations) in hash_map {
-            if locations.len() > 1 {
-                clone_id += 1;
-                clones.push(Clone {
-                    id: clone_id,
-                    length: self.window_size,
-                    locations: locations
-                        .into_iter()
-                        .map(|(start, end)| CloneLocation { file: file_path.clone(), start_line: start, end_line: end })
-                        .collect(),
-                    hash,
-                });
-            }
+        for (hash, length, starts) in spans {
+            clone_id += 1;
+            clones.push(Clone {
+                id: clone_id,
+                length,
+                locations: starts
+                    .into_iter()
+                    .map(|start| CloneLocation {
+                        file: file_path.clone(),
+                        start_line: significant_tokens[start].line,
+                        end_line: significant_tokens[start + length - 1].line,
+                    })
+                    .collect(),
+                hash,
+            });
         }
 
         Ok(clones)
@@ -97,59 +140,72 @@ impl CloneDetector {
 
     /// Detect clones across multiple files
     pub fn detect_across_files(&self, files: &[(PathBuf, String, Language)]) -> Result<Vec<Clone>> {
-        let mut global_hash_map: HashMap<u64, Vec<CloneLocation>> = HashMap::new();
-
-        for (file_path, source, language) in files {
-            let tokens = Tokenizer::new(source, *language).tokenize()?;
-            let significant_tokens: Vec<&Token> = tokens.iter().filter(|t| t.token_type.is_significant()).collect();
+        let mut owned_tokens = Vec::with_capacity(files.len());
+        for (_, source, language) in files {
+            owned_tokens.push(Tokenizer::new(source, *language).tokenize()?);
+        }
 
-            if significant_tokens.len() < self.window_size {
-                continue;
+        let file_data: Vec<FileData> = files
+            .iter()
+            .zip(owned_tokens.iter())
+            .map(|((path, _, _), tokens)| {
+                let significant_tokens: Vec<&Token<'_>> = tokens.iter().filter(|t| t.token_type.is_significant()).collect();
+                let token_hashes: Vec<u64> =
+                    significant_tokens.iter().map(|t| token_signature(t, self.mode)).collect();
+                FileData { path, significant_tokens, token_hashes }
+            })
+            .collect();
+
+        let mut global_hash_map: HashMap<u64, Vec<(usize, usize)>> = HashMap::new();
+
+        for (file_idx, data) in file_data.iter().enumerate() {
+            for (hash, start) in winnow(&data.token_hashes, self.window_size, self.winnow_window) {
+                global_hash_map.entry(hash).or_default().push((file_idx, start));
             }
+        }
 
-            let mut rh = RollingHash::new(self.window_size);
-
-            let token_hashes: Vec<u64> = significant_tokens.iter().map(|t| token_hash(&t.text)).collect();
+        let mut groups: Vec<(u64, usize, Vec<(usize, usize)>)> = global_hash_map
+            .into_iter()
+            .filter_map(|(hash, mut locs)| {
+                locs.sort_unstable();
+                locs.dedup();
+                if locs.len() < 2 {
+                    return None;
+                }
+                let length = extend_cross_file_match(&file_data, &locs, self.window_size);
+                Some((hash, length, locs))
+            })
+            .collect();
 
-            rh.init(&token_hashes[0..self.window_size]);
-            let start_line = significant_tokens[0].line;
-            let end_line = significant_tokens[self.window_size - 1].line;
-            global_hash_map.entry(rh.get()).or_default().push(CloneLocation {
-                file: file_path.clone(),
-                start_line,
-                end_line,
-            });
-
-            for i in self.window_size..token_hashes.len() {
-                let hash = rh.roll(token_hashes[i - self.window_size], token_hashes[i]);
-                let start_line = significant_tokens[i - self.window_size + 1].line;
-                let end_line = significant_tokens[i].line;
-                global_hash_map.entry(hash).or_default().push(CloneLocation {
-                    file: file_path.clone(),
-                    start_line,
-                    end_line,
-                });
-            }
-        }
+        groups.sort_by(|a, b| b.1.cmp(&a.1));
 
         let mut clones = Vec::new();
+        let mut accepted: Vec<(usize, Vec<(usize, usize)>)> = Vec::new();
         let mut clone_id = 0;
 
-        for (hash, mut locations) in global_hash_map {
-            if locations.len() > 1 {
-                locations.sort_by(|a, b| {
-                    a.file
-                        .cmp(&b.file)
-                        .then(a.start_line.cmp(&b.start_line))
-                        .then(a.end_line.cmp(&b.end_line))
-                });
-                locations.dedup();
-
-                if locations.len() > 1 {
-                    clone_id += 1;
-                    clones.push(Clone { id: clone_id, length: self.window_size, locations, hash });
+        'groups: for (hash, length, locs) in groups {
+            for (acc_len, acc_locs) in &accepted {
+                if is_contained(&locs, length, acc_locs, *acc_len) {
+                    continue 'groups;
                 }
             }
+
+            let mut locations: Vec<CloneLocation> = locs
+                .iter()
+                .map(|&(file_idx, start)| {
+                    let data = &file_data[file_idx];
+                    CloneLocation {
+                        file: data.path.clone(),
+                        start_line: data.significant_tokens[start].line,
+                        end_line: data.significant_tokens[start + length - 1].line,
+                    }
+                })
+                .collect();
+            locations.sort_by(|a, b| a.file.cmp(&b.file).then(a.start_line.cmp(&b.start_line)));
+
+            clone_id += 1;
+            clones.push(Clone { id: clone_id, length, locations, hash });
+            accepted.push((length, locs));
         }
 
         clones.sort_by(|a, b| b.locations.len().cmp(&a.locations.len()));
@@ -157,6 +213,136 @@ impl CloneDetector {
     }
 }
 
+/// Hash a token's text for matching purposes, honoring `mode`. Under
+/// [`DetectionMode::Normalized`], identifiers collapse to a shared
+/// placeholder so renamed variables/parameters still line up.
+fn token_signature(token: &Token<'_>, mode: DetectionMode) -> u64 {
+    match (mode, &token.token_type) {
+        (DetectionMode::Normalized, TokenType::Identifier(_)) => token_hash(NORMALIZED_IDENTIFIER_PLACEHOLDER),
+        _ => token_hash(token.text),
+    }
+}
+
+/// Per-file token data kept alive for the duration of a cross-file clone
+/// merge, so maximal spans can be reconstructed without re-tokenizing.
+struct FileData<'a> {
+    path: &'a PathBuf,
+    significant_tokens: Vec<&'a Token<'a>>,
+    token_hashes: Vec<u64>,
+}
+
+/// Reconstruct maximal clones from a single file's window-hash buckets.
+///
+/// Every matching window of `window_size` tokens gets its own bucket, so one
+/// long duplicated region shows up as dozens of overlapping buckets (one per
+/// starting offset). This extends each bucket's match as far as it holds,
+/// then drops any resulting span that's fully contained in a longer one,
+/// leaving one clone group per duplicated region.
+fn merge_maximal_spans(
+    token_hashes: &[u64], hash_map: HashMap<u64, Vec<usize>>, window_size: usize,
+) -> Vec<(u64, usize, Vec<usize>)> {
+    let mut groups: Vec<(u64, usize, Vec<usize>)> = hash_map
+        .into_iter()
+        .filter_map(|(hash, mut starts)| {
+            starts.sort_unstable();
+            starts.dedup();
+            if starts.len() < 2 {
+                return None;
+            }
+            let length = extend_match(token_hashes, &starts, window_size);
+            Some((hash, length, starts))
+        })
+        .collect();
+
+    groups.sort_by(|a, b| b.1.cmp(&a.1));
+
+    let mut accepted: Vec<(u64, usize, Vec<usize>)> = Vec::new();
+    'groups: for (hash, length, starts) in groups {
+        for (_, acc_len, acc_starts) in &accepted {
+            if starts.len() == acc_starts.len()
+                && starts
+                    .iter()
+                    .zip(acc_starts)
+                    .all(|(&start, &acc_start)| start >= acc_start && start + length <= acc_start + acc_len)
+            {
+                continue 'groups;
+            }
+        }
+        accepted.push((hash, length, starts));
+    }
+
+    accepted
+}
+
+/// Extend every start index in a bucket forward in lockstep while all
+/// occurrences keep agreeing token-for-token, returning the maximal shared
+/// length (at least `window_size`, since a shared bucket hash already means
+/// the first `window_size` tokens match).
+fn extend_match(token_hashes: &[u64], starts: &[usize], window_size: usize) -> usize {
+    let mut length = window_size;
+
+    loop {
+        let Some(&reference) = starts.first() else { break };
+        let ref_idx = reference + length;
+        if ref_idx >= token_hashes.len() {
+            break;
+        }
+
+        let ref_hash = token_hashes[ref_idx];
+        let all_match = starts[1..].iter().all(|&start| {
+            let idx = start + length;
+            idx < token_hashes.len() && token_hashes[idx] == ref_hash
+        });
+
+        if !all_match {
+            break;
+        }
+
+        length += 1;
+    }
+
+    length
+}
+
+/// Cross-file counterpart of [`extend_match`], comparing token hashes within
+/// each occurrence's own file.
+fn extend_cross_file_match(file_data: &[FileData], locs: &[(usize, usize)], window_size: usize) -> usize {
+    let mut length = window_size;
+
+    loop {
+        let Some(&(ref_file, ref_start)) = locs.first() else { break };
+        let ref_hashes = &file_data[ref_file].token_hashes;
+        let ref_idx = ref_start + length;
+        if ref_idx >= ref_hashes.len() {
+            break;
+        }
+
+        let ref_hash = ref_hashes[ref_idx];
+        let all_match = locs[1..].iter().all(|&(file_idx, start)| {
+            let hashes = &file_data[file_idx].token_hashes;
+            let idx = start + length;
+            idx < hashes.len() && hashes[idx] == ref_hash
+        });
+
+        if !all_match {
+            break;
+        }
+
+        length += 1;
+    }
+
+    length
+}
+
+/// True if every occurrence in `(locs, length)` falls within the matching
+/// occurrence (same file, same position in the list) of `(acc_locs, acc_len)`
+fn is_contained(locs: &[(usize, usize)], length: usize, acc_locs: &[(usize, usize)], acc_len: usize) -> bool {
+    locs.len() == acc_locs.len()
+        && locs.iter().zip(acc_locs).all(|(&(file, start), &(acc_file, acc_start))| {
+            file == acc_file && start >= acc_start && start + length <= acc_start + acc_len
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -207,6 +393,32 @@ fn process_b() {
         }
     }
 
+    #[test]
+    fn test_overlapping_windows_merge_into_one_maximal_clone() {
+        let source = r#"
+fn process_a() {
+    let x = input.get();
+    let y = x * 2;
+    let z = y + 5;
+    return z;
+}
+
+fn process_b() {
+    let x = input.get();
+    let y = x * 2;
+    let z = y + 5;
+    return z;
+}
+"#;
+        let detector = CloneDetector::new(5);
+        let clones = detector
+            .detect_in_file(source, Language::Rust, PathBuf::from("test.rs"))
+            .unwrap();
+
+        assert_eq!(clones.len(), 1);
+        assert!(clones[0].length > 5);
+    }
+
     #[test]
     fn test_across_files() {
         let file1 = r#"
@@ -241,6 +453,63 @@ fn another() {
         }
     }
 
+    #[test]
+    fn test_across_files_merges_into_one_maximal_clone() {
+        let file1 = r#"
+fn helper() {
+    for i in 0..10 {
+        println!("{}", i);
+    }
+}
+"#;
+        let file2 = r#"
+fn another() {
+    for i in 0..10 {
+        println!("{}", i);
+    }
+}
+"#;
+
+        let files = vec![
+            (PathBuf::from("file1.rs"), file1.to_string(), Language::Rust),
+            (PathBuf::from("file2.rs"), file2.to_string(), Language::Rust),
+        ];
+
+        let detector = CloneDetector::new(5);
+        let clones = detector.detect_across_files(&files).unwrap();
+
+        assert_eq!(clones.len(), 1);
+        assert!(clones[0].length > 5);
+    }
+
+    #[test]
+    fn test_normalized_mode_detects_renamed_variable_clone() {
+        let source = r#"
+fn alpha() {
+    let one = two + three;
+}
+
+fn beta() {
+    let four = five + six;
+}
+"#;
+        let exact = CloneDetector::new(5);
+        let exact_clones = exact
+            .detect_in_file(source, Language::Rust, PathBuf::from("test.rs"))
+            .unwrap();
+        assert!(exact_clones.is_empty());
+
+        let normalized = CloneDetector::new(5).with_mode(DetectionMode::Normalized);
+        let normalized_clones = normalized
+            .detect_in_file(source, Language::Rust, PathBuf::from("test.rs"))
+            .unwrap();
+
+        assert!(!normalized_clones.is_empty());
+        for clone in &normalized_clones {
+            assert!(clone.locations.len() >= 2);
+        }
+    }
+
     #[test]
     fn test_min_tokens_threshold() {
         let source = "let x = 5; let y = 10; let x = 5; let y = 10;";
@@ -1,5 +1,7 @@
 pub mod detector;
 pub mod rolling_hash;
+pub mod winnow;
 
-pub use detector::{Clone, CloneDetector, CloneLocation};
+pub use detector::{Clone, CloneDetector, CloneLocation, DetectionMode};
 pub use rolling_hash::RollingHash;
+pub use winnow::winnow;
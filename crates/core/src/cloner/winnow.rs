@@ -0,0 +1,140 @@
+use crate::cloner::rolling_hash::RollingHash;
+
+/// Select a sparse, position-tagged set of fingerprints from a token-hash
+/// sequence using the winnowing algorithm (Schleimer, Wilkerson & Aiken,
+/// "Winnowing: Local Algorithms for Document Fingerprinting").
+///
+/// `k` is the k-gram size fed to the underlying [`RollingHash`] (i.e. the
+/// `min_tokens` a clone must span to be guaranteed a shared fingerprint), and
+/// `w` is the number of consecutive k-gram hashes considered per window. Any
+/// shared token substring of length at least `w + k - 1` is guaranteed to
+/// produce at least one common fingerprint between occurrences, while the
+/// number of fingerprints emitted is roughly `1/w` of the k-gram count.
+///
+/// Within each window the minimum hash is selected, breaking ties by
+/// preferring the rightmost (most recent) position; a fingerprint is only
+/// emitted when the selected position differs from the last one emitted, so
+/// a single minimum held across several overlapping windows is reported once.
+pub fn winnow(token_hashes: &[u64], k: usize, w: usize) -> Vec<(u64, usize)> {
+    if k == 0 || w == 0 || token_hashes.len() < k {
+        return Vec::new();
+    }
+
+    let kgram_hashes = kgram_hashes(token_hashes, k);
+    if kgram_hashes.len() < w {
+        return select_min(&kgram_hashes).into_iter().collect();
+    }
+
+    let mut fingerprints = Vec::new();
+    let mut last_selected: Option<usize> = None;
+
+    for window_start in 0..=(kgram_hashes.len() - w) {
+        let window = &kgram_hashes[window_start..window_start + w];
+        let (min_offset, &min_hash) = window
+            .iter()
+            .enumerate()
+            .rev()
+            .min_by_key(|(_, hash)| **hash)
+            .expect("window is non-empty");
+        let selected = window_start + min_offset;
+
+        if last_selected != Some(selected) {
+            fingerprints.push((min_hash, selected));
+            last_selected = Some(selected);
+        }
+    }
+
+    fingerprints
+}
+
+/// Slide `RollingHash` over `token_hashes` to produce one k-gram hash per
+/// start position, i.e. `hashes[i]` covers `token_hashes[i..i + k]`.
+fn kgram_hashes(token_hashes: &[u64], k: usize) -> Vec<u64> {
+    let mut rh = RollingHash::new(k);
+    let mut hashes = Vec::with_capacity(token_hashes.len().saturating_sub(k) + 1);
+
+    rh.init(&token_hashes[0..k]);
+    hashes.push(rh.get());
+
+    for i in k..token_hashes.len() {
+        hashes.push(rh.roll(token_hashes[i - k], token_hashes[i]));
+    }
+
+    hashes
+}
+
+/// Fallback for sequences shorter than one full window: the whole k-gram
+/// hash list acts as a single window, so just pick its minimum.
+fn select_min(kgram_hashes: &[u64]) -> Option<(u64, usize)> {
+    kgram_hashes
+        .iter()
+        .enumerate()
+        .rev()
+        .min_by_key(|(_, hash)| **hash)
+        .map(|(idx, &hash)| (hash, idx))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_winnow_empty_on_short_input() {
+        assert_eq!(winnow(&[1, 2], 5, 4), Vec::new());
+    }
+
+    #[test]
+    fn test_winnow_fewer_kgrams_than_window_picks_single_min() {
+        let tokens = [5, 1, 3];
+        let fingerprints = winnow(&tokens, 1, 10);
+
+        assert_eq!(fingerprints.len(), 1);
+        assert_eq!(fingerprints[0].1, 1);
+    }
+
+    #[test]
+    fn test_winnow_produces_fewer_fingerprints_than_kgrams() {
+        let tokens: Vec<u64> = (0..50).collect();
+        let fingerprints = winnow(&tokens, 4, 5);
+
+        assert!(!fingerprints.is_empty());
+        assert!(fingerprints.len() < tokens.len() - 4 + 1);
+    }
+
+    #[test]
+    fn test_winnow_ties_prefer_rightmost_position() {
+        let tokens = [1, 1, 1, 1, 1];
+        let fingerprints = winnow(&tokens, 1, 3);
+
+        for (_, pos) in &fingerprints {
+            assert!(*pos > 0 || fingerprints.len() == 1);
+        }
+    }
+
+    #[test]
+    fn test_winnow_same_sequence_yields_same_fingerprints() {
+        let tokens = [3, 1, 4, 1, 5, 9, 2, 6, 5, 3];
+
+        assert_eq!(winnow(&tokens, 3, 4), winnow(&tokens, 3, 4));
+    }
+
+    #[test]
+    fn test_shared_substring_produces_common_fingerprint() {
+        let k = 3;
+        let w = 4;
+        let shared = [10u64, 20, 30, 40, 50, 60];
+
+        let mut seq_a = vec![1, 2];
+        seq_a.extend_from_slice(&shared);
+        seq_a.extend_from_slice(&[7, 8]);
+
+        let mut seq_b = vec![9];
+        seq_b.extend_from_slice(&shared);
+        seq_b.extend_from_slice(&[99]);
+
+        let fp_a: std::collections::HashSet<u64> = winnow(&seq_a, k, w).into_iter().map(|(hash, _)| hash).collect();
+        let fp_b: std::collections::HashSet<u64> = winnow(&seq_b, k, w).into_iter().map(|(hash, _)| hash).collect();
+
+        assert!(fp_a.intersection(&fp_b).next().is_some(), "shared substring should yield a common fingerprint");
+    }
+}
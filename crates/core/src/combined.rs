@@ -0,0 +1,171 @@
+use crate::Result;
+use crate::complexity::loc::{self, FileLocReport, LineKind, LocMetrics};
+use crate::coverage::{FileCoverage, VfsPath};
+use crate::loader::SourceFile;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Per-file report joining LOC metrics with coverage miss ranges, narrowed to
+/// lines that are actually code (as opposed to raw LCOV miss totals, which
+/// also count blank and comment lines sitting inside a miss range).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileReport {
+    pub path: PathBuf,
+    pub loc: LocMetrics,
+    pub coverage_miss_ranges: Vec<(u32, u32)>,
+    /// Lines within `coverage_miss_ranges` that are neither blank nor
+    /// comment-only
+    pub uncovered_logical_lines: usize,
+}
+
+/// Ranking criteria for combined reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RankBy {
+    /// Rank by uncovered logical lines (the headline metric of this report)
+    UncoveredLogical,
+    /// Rank by logical lines of code
+    Logical,
+    /// Rank by physical lines of code
+    Physical,
+}
+
+impl RankBy {
+    /// Get the value from a FileReport based on ranking criteria
+    pub fn value_from(&self, report: &FileReport) -> usize {
+        match self {
+            Self::UncoveredLogical => report.uncovered_logical_lines,
+            Self::Logical => report.loc.logical,
+            Self::Physical => report.loc.physical,
+        }
+    }
+}
+
+/// Join per-file LOC and coverage data on normalized path, computing the
+/// number of genuinely uncovered logical lines per file.
+///
+/// `sources` supplies both the path-matching key and the file content needed
+/// to classify lines as code/comment/blank; a file with no coverage data
+/// simply reports zero uncovered logical lines.
+pub fn build_reports(
+    sources: &[SourceFile],
+    loc_files: &[FileLocReport],
+    coverage_files: &[FileCoverage],
+    rank_by: RankBy,
+) -> Result<Vec<FileReport>> {
+    let mut reports = Vec::new();
+
+    for source in sources {
+        let key = vfs_key(&source.path);
+
+        let Some(loc_file) = loc_files.iter().find(|f| vfs_key(&f.path) == key) else {
+            continue;
+        };
+
+        let coverage = coverage_files.iter().find(|f| vfs_key(Path::new(&f.path)) == key);
+
+        let (coverage_miss_ranges, uncovered_logical_lines) = match coverage {
+            Some(cov) => {
+                let line_kinds = loc::classify_lines(&source.content, source.language)?;
+                let uncovered = count_uncovered_logical_lines(&cov.miss_ranges, &line_kinds);
+                (cov.miss_ranges.clone(), uncovered)
+            }
+            None => (Vec::new(), 0),
+        };
+
+        reports.push(FileReport {
+            path: source.path.clone(),
+            loc: loc_file.metrics.clone(),
+            coverage_miss_ranges,
+            uncovered_logical_lines,
+        });
+    }
+
+    reports.sort_by(|a, b| rank_by.value_from(b).cmp(&rank_by.value_from(a)));
+    Ok(reports)
+}
+
+/// Serialize a ranked list of [`FileReport`]s to JSON
+pub fn reports_to_json(reports: &[FileReport]) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(reports)
+}
+
+fn vfs_key(path: &Path) -> String {
+    VfsPath::from(path).to_canonical_string()
+}
+
+/// Count 1-based `miss_ranges` lines that classify as [`LineKind::Code`]
+fn count_uncovered_logical_lines(miss_ranges: &[(u32, u32)], line_kinds: &[LineKind]) -> usize {
+    let mut count = 0;
+
+    for &(start, end) in miss_ranges {
+        for line in start..=end {
+            let idx = (line as usize).saturating_sub(1);
+            if line_kinds.get(idx) == Some(&LineKind::Code) {
+                count += 1;
+            }
+        }
+    }
+
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenizer::Language;
+    use std::collections::BTreeMap;
+
+    fn source(path: &str, content: &str) -> SourceFile {
+        SourceFile { path: PathBuf::from(path), content: content.to_string(), language: Language::Rust }
+    }
+
+    fn loc_file(path: &str, content: &str) -> FileLocReport {
+        FileLocReport { path: PathBuf::from(path), metrics: LocMetrics::calculate(content, Language::Rust).unwrap() }
+    }
+
+    #[test]
+    fn test_build_reports_excludes_blank_and_comment_lines_from_miss_range() {
+        let content = "fn main() {\n    // a comment\n\n    println!(\"hi\");\n}\n";
+        let mut lines = BTreeMap::new();
+        for line in 1..=5u32 {
+            lines.insert(line, 0u64);
+        }
+
+        let sources = vec![source("a.rs", content)];
+        let loc_files = vec![loc_file("a.rs", content)];
+        let coverage_files = vec![FileCoverage::new("a.rs".to_string(), lines)];
+
+        let reports = build_reports(&sources, &loc_files, &coverage_files, RankBy::UncoveredLogical).unwrap();
+
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].uncovered_logical_lines, 3);
+    }
+
+    #[test]
+    fn test_build_reports_no_coverage_data_yields_zero_uncovered() {
+        let content = "fn main() {}\n";
+        let sources = vec![source("a.rs", content)];
+        let loc_files = vec![loc_file("a.rs", content)];
+
+        let reports = build_reports(&sources, &loc_files, &[], RankBy::UncoveredLogical).unwrap();
+
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].uncovered_logical_lines, 0);
+        assert!(reports[0].coverage_miss_ranges.is_empty());
+    }
+
+    #[test]
+    fn test_build_reports_ranks_by_uncovered_logical_lines() {
+        let quiet = "fn quiet() {}\n";
+        let mut noisy_lines = BTreeMap::new();
+        noisy_lines.insert(1, 0u64);
+
+        let sources = vec![source("quiet.rs", quiet), source("noisy.rs", "fn noisy() {}\n")];
+        let loc_files = vec![loc_file("quiet.rs", quiet), loc_file("noisy.rs", "fn noisy() {}\n")];
+        let coverage_files = vec![FileCoverage::new("noisy.rs".to_string(), noisy_lines)];
+
+        let reports = build_reports(&sources, &loc_files, &coverage_files, RankBy::UncoveredLogical).unwrap();
+
+        assert_eq!(reports[0].path, PathBuf::from("noisy.rs"));
+    }
+}
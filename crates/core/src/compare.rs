@@ -0,0 +1,302 @@
+use crate::complexity::loc::LocReport;
+use crate::coverage::{CoverageReport, VfsPath};
+use crate::error::{MccabreError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A point-in-time snapshot of analysis results, saved to disk so a later run
+/// can be diffed against it to catch complexity/coverage regressions.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Baseline {
+    pub loc: Option<LocReport>,
+    pub coverage: Option<CoverageReport>,
+}
+
+impl Baseline {
+    pub fn new(loc: Option<LocReport>, coverage: Option<CoverageReport>) -> Self {
+        Self { loc, coverage }
+    }
+
+    /// Load a previously saved baseline snapshot
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = fs::read_to_string(path.as_ref())
+            .map_err(|e| MccabreError::FileRead { path: path.as_ref().to_path_buf(), source: e })?;
+
+        serde_json::from_str(&content).map_err(|e| MccabreError::InvalidConfig(e.to_string()))
+    }
+
+    /// Save this snapshot to disk
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let content = serde_json::to_string_pretty(self).map_err(|e| MccabreError::InvalidConfig(e.to_string()))?;
+
+        fs::write(path.as_ref(), content)
+            .map_err(|e| MccabreError::FileRead { path: path.as_ref().to_path_buf(), source: e })?;
+
+        Ok(())
+    }
+}
+
+/// Per-file logical LOC delta between two runs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocDelta {
+    pub path: PathBuf,
+    pub logical_before: Option<usize>,
+    pub logical_after: Option<usize>,
+    pub logical_change: i64,
+}
+
+/// Per-file coverage miss-count delta between two runs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoverageDelta {
+    pub path: String,
+    pub miss_before: Option<usize>,
+    pub miss_after: Option<usize>,
+    pub miss_change: i64,
+}
+
+/// Thresholds that decide whether a regression diff should fail
+#[derive(Debug, Clone, Copy)]
+pub struct RegressionPolicy {
+    /// Fail if total coverage rate drops by more than this many percentage points
+    pub max_coverage_drop_pct: Option<f64>,
+    /// Fail if any single file's miss count increases at all
+    pub fail_on_new_misses: bool,
+}
+
+impl Default for RegressionPolicy {
+    fn default() -> Self {
+        Self { max_coverage_drop_pct: None, fail_on_new_misses: false }
+    }
+}
+
+/// Result of diffing a baseline snapshot against a fresh run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegressionReport {
+    pub loc_deltas: Vec<LocDelta>,
+    pub coverage_deltas: Vec<CoverageDelta>,
+    pub total_logical_change: i64,
+    pub coverage_rate_before: f64,
+    pub coverage_rate_after: f64,
+    pub failures: Vec<String>,
+    pub passed: bool,
+}
+
+impl RegressionReport {
+    /// Serialize to JSON
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// Diff `current` against `baseline` and decide pass/fail per `policy`
+pub fn check_regressions(baseline: &Baseline, current: &Baseline, policy: &RegressionPolicy) -> RegressionReport {
+    let loc_deltas = diff_loc(baseline.loc.as_ref(), current.loc.as_ref());
+    let coverage_deltas = diff_coverage(baseline.coverage.as_ref(), current.coverage.as_ref());
+
+    let total_logical_change = loc_deltas.iter().map(|d| d.logical_change).sum();
+    let coverage_rate_before = baseline.coverage.as_ref().map(|c| c.totals.rate).unwrap_or(0.0);
+    let coverage_rate_after = current.coverage.as_ref().map(|c| c.totals.rate).unwrap_or(0.0);
+
+    let mut failures = Vec::new();
+
+    if let Some(max_drop) = policy.max_coverage_drop_pct {
+        let drop = coverage_rate_before - coverage_rate_after;
+        if drop > max_drop {
+            failures.push(format!(
+                "coverage rate dropped {drop:.2} percentage points (from {coverage_rate_before:.2}% to {coverage_rate_after:.2}%), exceeding the allowed {max_drop:.2}"
+            ));
+        }
+    }
+
+    if policy.fail_on_new_misses {
+        for delta in &coverage_deltas {
+            if delta.miss_change > 0 {
+                failures.push(format!(
+                    "{} gained {} new uncovered line(s)",
+                    delta.path, delta.miss_change
+                ));
+            }
+        }
+    }
+
+    let passed = failures.is_empty();
+
+    RegressionReport {
+        loc_deltas,
+        coverage_deltas,
+        total_logical_change,
+        coverage_rate_before,
+        coverage_rate_after,
+        failures,
+        passed,
+    }
+}
+
+/// Normalize a path to the same canonical key `combined::build_reports` uses,
+/// so baseline and current snapshots taken from different working
+/// directories (or an LCOV `SF:` path with a different relative anchor) still
+/// correlate to the same file.
+fn vfs_key(path: &Path) -> String {
+    VfsPath::from(path).to_canonical_string()
+}
+
+fn diff_loc(baseline: Option<&LocReport>, current: Option<&LocReport>) -> Vec<LocDelta> {
+    let before: HashMap<String, (PathBuf, usize)> = baseline
+        .map(|r| r.files.iter().map(|f| (vfs_key(&f.path), (f.path.clone(), f.metrics.logical))).collect())
+        .unwrap_or_default();
+    let after: HashMap<String, (PathBuf, usize)> = current
+        .map(|r| r.files.iter().map(|f| (vfs_key(&f.path), (f.path.clone(), f.metrics.logical))).collect())
+        .unwrap_or_default();
+
+    let mut keys: Vec<&String> = before.keys().chain(after.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    keys.into_iter()
+        .map(|key| {
+            let logical_before = before.get(key).map(|(_, logical)| *logical);
+            let logical_after = after.get(key).map(|(_, logical)| *logical);
+            let logical_change = logical_after.unwrap_or(0) as i64 - logical_before.unwrap_or(0) as i64;
+            let path = after
+                .get(key)
+                .or_else(|| before.get(key))
+                .map(|(path, _)| path.clone())
+                .expect("key came from before or after");
+
+            LocDelta { path, logical_before, logical_after, logical_change }
+        })
+        .collect()
+}
+
+fn diff_coverage(baseline: Option<&CoverageReport>, current: Option<&CoverageReport>) -> Vec<CoverageDelta> {
+    let before: HashMap<String, (String, usize)> = baseline
+        .map(|r| r.files.iter().map(|f| (vfs_key(Path::new(&f.path)), (f.path.clone(), f.summary.miss))).collect())
+        .unwrap_or_default();
+    let after: HashMap<String, (String, usize)> = current
+        .map(|r| r.files.iter().map(|f| (vfs_key(Path::new(&f.path)), (f.path.clone(), f.summary.miss))).collect())
+        .unwrap_or_default();
+
+    let mut keys: Vec<&String> = before.keys().chain(after.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    keys.into_iter()
+        .map(|key| {
+            let miss_before = before.get(key).map(|(_, miss)| *miss);
+            let miss_after = after.get(key).map(|(_, miss)| *miss);
+            let miss_change = miss_after.unwrap_or(0) as i64 - miss_before.unwrap_or(0) as i64;
+            let path = after
+                .get(key)
+                .or_else(|| before.get(key))
+                .map(|(path, _)| path.clone())
+                .expect("key came from before or after");
+
+            CoverageDelta { path, miss_before, miss_after, miss_change }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::complexity::loc::{FileLocReport, LocMetrics, RankBy};
+    use crate::coverage::FileCoverage;
+    use std::collections::BTreeMap;
+    use tempfile::TempDir;
+
+    fn loc_report(logical: usize) -> LocReport {
+        let files = vec![FileLocReport {
+            path: PathBuf::from("a.rs"),
+            metrics: LocMetrics { physical: logical + 2, logical, comments: 1, blank: 1 },
+        }];
+        LocReport::new(files, RankBy::Logical, false)
+    }
+
+    fn coverage_report(second_line_hit_count: u64) -> CoverageReport {
+        let mut lines = BTreeMap::new();
+        lines.insert(1, 10);
+        lines.insert(2, second_line_hit_count);
+        CoverageReport::new(vec![FileCoverage::new("a.rs".to_string(), lines)])
+    }
+
+    #[test]
+    fn test_baseline_save_and_load_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("baseline.json");
+
+        let baseline = Baseline::new(Some(loc_report(10)), Some(coverage_report(0)));
+        baseline.save(&path).unwrap();
+
+        let loaded = Baseline::load(&path).unwrap();
+        assert_eq!(loaded.loc.unwrap().summary.total_logical, 10);
+    }
+
+    #[test]
+    fn test_diff_loc_detects_logical_loc_increase() {
+        let before = loc_report(10);
+        let after = loc_report(15);
+
+        let deltas = diff_loc(Some(&before), Some(&after));
+        assert_eq!(deltas.len(), 1);
+        assert_eq!(deltas[0].logical_change, 5);
+    }
+
+    #[test]
+    fn test_diff_coverage_detects_new_miss() {
+        let before = coverage_report(10);
+        let after = coverage_report(0);
+
+        let deltas = diff_coverage(Some(&before), Some(&after));
+        assert_eq!(deltas.len(), 1);
+        assert_eq!(deltas[0].miss_change, 1);
+    }
+
+    #[test]
+    fn test_diff_loc_correlates_across_differing_relative_anchors() {
+        let before = LocReport::new(
+            vec![FileLocReport {
+                path: PathBuf::from("./a.rs"),
+                metrics: LocMetrics { physical: 12, logical: 10, comments: 1, blank: 1 },
+            }],
+            RankBy::Logical,
+            false,
+        );
+        let after = LocReport::new(
+            vec![FileLocReport {
+                path: PathBuf::from("a.rs"),
+                metrics: LocMetrics { physical: 17, logical: 15, comments: 1, blank: 1 },
+            }],
+            RankBy::Logical,
+            false,
+        );
+
+        let deltas = diff_loc(Some(&before), Some(&after));
+        assert_eq!(deltas.len(), 1);
+        assert_eq!(deltas[0].logical_change, 5);
+    }
+
+    #[test]
+    fn test_check_regressions_fails_on_coverage_drop() {
+        let baseline = Baseline::new(None, Some(coverage_report(10)));
+        let current = Baseline::new(None, Some(coverage_report(0)));
+
+        let policy = RegressionPolicy { max_coverage_drop_pct: Some(1.0), fail_on_new_misses: false };
+        let report = check_regressions(&baseline, &current, &policy);
+
+        assert!(!report.passed);
+        assert_eq!(report.failures.len(), 1);
+    }
+
+    #[test]
+    fn test_check_regressions_passes_with_no_policy() {
+        let baseline = Baseline::new(Some(loc_report(10)), None);
+        let current = Baseline::new(Some(loc_report(5)), None);
+
+        let report = check_regressions(&baseline, &current, &RegressionPolicy::default());
+
+        assert!(report.passed);
+        assert_eq!(report.total_logical_change, -5);
+    }
+}
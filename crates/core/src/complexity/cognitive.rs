@@ -0,0 +1,286 @@
+use super::cyclomatic::{CyclomaticMetrics, Severity};
+use crate::Result;
+use crate::tokenizer::{Language, Token, TokenType, Tokenizer};
+use serde::{Deserialize, Serialize};
+
+/// Cognitive Complexity metrics for a file
+///
+/// Unlike cyclomatic complexity's flat "one point per decision", this
+/// weights a decision by how deeply it's nested, so deeply nested code scores
+/// higher than flat code with the same number of conditions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CognitiveMetrics {
+    /// Overall file cognitive score
+    pub file_score: usize,
+    /// Individual function scores (if we can detect them)
+    pub functions: Vec<FunctionCognitive>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionCognitive {
+    /// Function name (if identifiable)
+    pub name: String,
+    /// Cognitive complexity score
+    pub score: usize,
+    /// Line number where function starts
+    pub line: usize,
+}
+
+impl CognitiveMetrics {
+    /// Calculate cognitive complexity from source code
+    ///
+    /// Maintains a running score and a nesting level tracked via brace depth.
+    /// Each structure that breaks linear flow (`if`, ternary `?`, `switch`/`match`,
+    /// `for`, `while`, `loop`, `catch`) adds `1 + nesting` and increments nesting
+    /// for its body; `else`/`else if`/`finally` add a flat `1` with no nesting
+    /// penalty. A run of binary logical operators adds `1` only when the
+    /// operator alternates (`a && b && c` = 1, `a && b || c` = 2). Direct
+    /// recursion (a call whose identifier matches the enclosing function name)
+    /// adds `1`.
+    pub fn calculate(source: &str, language: Language) -> Result<Self> {
+        let tokens = Tokenizer::new(source, language).tokenize()?;
+        let significant: Vec<&Token<'_>> = tokens.iter().filter(|t| t.token_type.is_significant()).collect();
+        let file_score = Self::score_tokens(&significant, language, None);
+
+        let functions = CyclomaticMetrics::detect_function_spans(&tokens, language)
+            .into_iter()
+            .map(|span| {
+                let body: Vec<&Token<'_>> = tokens[span.body_start_idx..=span.body_end_idx]
+                    .iter()
+                    .filter(|t| t.token_type.is_significant())
+                    .collect();
+                let score = Self::score_tokens(&body, language, Some(span.name.as_str()));
+
+                FunctionCognitive { name: span.name, score, line: span.line }
+            })
+            .collect();
+
+        Ok(CognitiveMetrics { file_score, functions })
+    }
+
+    /// Fold the cognitive scoring rules over a (pre-filtered, significant-only)
+    /// token slice. `enclosing_fn` enables direct-recursion detection when
+    /// scoring a single function's body.
+    fn score_tokens(tokens: &[&Token<'_>], language: Language, enclosing_fn: Option<&str>) -> usize {
+        let mut score = 0usize;
+        let mut nesting: usize = 0;
+        let mut nesting_debt: Vec<usize> = Vec::new();
+        let mut pending_body = false;
+        let mut current_logical_op: Option<&str> = None;
+
+        let mut i = 0;
+        while i < tokens.len() {
+            let token_type = &tokens[i].token_type;
+
+            match token_type {
+                TokenType::Else => {
+                    score += 1;
+                    if tokens.get(i + 1).is_some_and(|t| matches!(t.token_type, TokenType::If)) {
+                        i += 1;
+                    }
+                }
+                TokenType::Identifier("finally") => {
+                    score += 1;
+                }
+                TokenType::Ternary if token_type.is_decision_point(language) => {
+                    score += 1 + nesting;
+                }
+                _ if is_nesting_structure(token_type, language) => {
+                    score += 1 + nesting;
+                    nesting += 1;
+                    pending_body = true;
+                }
+                TokenType::LeftBrace => {
+                    nesting_debt.push(if pending_body { 1 } else { 0 });
+                    pending_body = false;
+                    current_logical_op = None;
+                }
+                TokenType::RightBrace => {
+                    nesting = nesting.saturating_sub(nesting_debt.pop().unwrap_or(0));
+                    current_logical_op = None;
+                }
+                TokenType::Semicolon => {
+                    current_logical_op = None;
+                }
+                TokenType::LogicalAnd | TokenType::LogicalOr if token_type.is_decision_point(language) => {
+                    let op = if matches!(token_type, TokenType::LogicalAnd) { "&&" } else { "||" };
+                    if current_logical_op != Some(op) {
+                        score += 1;
+                        current_logical_op = Some(op);
+                    }
+                }
+                TokenType::Identifier(name)
+                    if enclosing_fn == Some(name) && tokens.get(i + 1).is_some_and(|t| matches!(t.token_type, TokenType::LeftParen)) =>
+                {
+                    score += 1;
+                }
+                _ => {}
+            }
+
+            i += 1;
+        }
+
+        score
+    }
+
+    /// Get severity level based on cognitive-specific thresholds, lower than
+    /// cyclomatic's since the nesting penalty makes scores climb faster for
+    /// deeply nested code.
+    pub fn severity(&self) -> Severity {
+        match self.file_score {
+            0..=5 => Severity::Low,
+            6..=10 => Severity::Moderate,
+            11..=20 => Severity::High,
+            _ => Severity::VeryHigh,
+        }
+    }
+}
+
+/// Whether `token_type` is a block structure that both scores `1 + nesting`
+/// and increments nesting for its body (as opposed to `else`/ternary/logical
+/// operators, which score but never nest).
+fn is_nesting_structure(token_type: &TokenType, language: Language) -> bool {
+    matches!(
+        token_type,
+        TokenType::If
+            | TokenType::For
+            | TokenType::While
+            | TokenType::Loop
+            | TokenType::Switch
+            | TokenType::Match
+            | TokenType::Catch
+    ) && token_type.is_decision_point(language)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flat_code_scores_low() {
+        let source = r#"
+fn simple() {
+    let x = 5;
+    return x;
+}
+"#;
+        let metrics = CognitiveMetrics::calculate(source, Language::Rust).unwrap();
+        assert_eq!(metrics.file_score, 0);
+        assert_eq!(metrics.severity(), Severity::Low);
+    }
+
+    #[test]
+    fn test_single_if_scores_one() {
+        let source = r#"
+fn check(x: i32) {
+    if x > 5 {
+        println!("big");
+    }
+}
+"#;
+        let metrics = CognitiveMetrics::calculate(source, Language::Rust).unwrap();
+        assert_eq!(metrics.file_score, 1);
+    }
+
+    #[test]
+    fn test_nesting_penalty_compounds() {
+        let source = r#"
+fn complex(x: i32) {
+    if x > 0 {
+        if x > 10 {
+            if x > 20 {
+                x;
+            }
+        }
+    }
+}
+"#;
+        let metrics = CognitiveMetrics::calculate(source, Language::Rust).unwrap();
+        assert_eq!(metrics.file_score, 1 + 2 + 3);
+    }
+
+    #[test]
+    fn test_else_if_chain_is_flat() {
+        let source = r#"
+fn classify(x: i32) {
+    if x > 0 {
+        x;
+    } else if x < 0 {
+        x;
+    } else {
+        x;
+    }
+}
+"#;
+        let metrics = CognitiveMetrics::calculate(source, Language::Rust).unwrap();
+        assert_eq!(metrics.file_score, 1 + 1 + 1);
+    }
+
+    #[test]
+    fn test_alternating_logical_operators_score_higher() {
+        let same_op = "let y = a && b && c;";
+        let metrics = CognitiveMetrics::calculate(same_op, Language::JavaScript).unwrap();
+        assert_eq!(metrics.file_score, 1);
+
+        let alternating = "let y = a && b || c;";
+        let metrics = CognitiveMetrics::calculate(alternating, Language::JavaScript).unwrap();
+        assert_eq!(metrics.file_score, 2);
+    }
+
+    #[test]
+    fn test_direct_recursion_adds_one() {
+        let source = r#"
+fn factorial(n: i32) {
+    if n <= 1 {
+        n;
+    } else {
+        factorial(n - 1);
+    }
+}
+"#;
+        let metrics = CognitiveMetrics::calculate(source, Language::Rust).unwrap();
+        let func = metrics.functions.iter().find(|f| f.name == "factorial").unwrap();
+        assert_eq!(func.score, 1 + 1 + 1);
+    }
+
+    #[test]
+    fn test_per_function_scores_reuse_detected_spans() {
+        let source = r#"
+fn simple() {
+    let x = 5;
+}
+
+fn complex() {
+    if true {
+        while false {
+            loop { break; }
+        }
+    }
+}
+"#;
+        let metrics = CognitiveMetrics::calculate(source, Language::Rust).unwrap();
+        assert_eq!(metrics.functions.len(), 2);
+        assert_eq!(metrics.functions[0].score, 0);
+        assert_eq!(metrics.functions[1].score, 1 + 2 + 3);
+    }
+
+    #[test]
+    fn test_severity_levels() {
+        assert_eq!(
+            CognitiveMetrics { file_score: 3, functions: vec![] }.severity(),
+            Severity::Low
+        );
+        assert_eq!(
+            CognitiveMetrics { file_score: 8, functions: vec![] }.severity(),
+            Severity::Moderate
+        );
+        assert_eq!(
+            CognitiveMetrics { file_score: 15, functions: vec![] }.severity(),
+            Severity::High
+        );
+        assert_eq!(
+            CognitiveMetrics { file_score: 30, functions: vec![] }.severity(),
+            Severity::VeryHigh
+        );
+    }
+}
@@ -27,6 +27,20 @@ pub struct FunctionComplexity {
     pub complexity: usize,
     /// Line number where function starts
     pub line: usize,
+    /// Line number of the function's closing brace
+    pub end_line: usize,
+}
+
+/// A detected function's name, starting line, and the token-index span of its
+/// body (the opening brace through its matching closing brace, inclusive).
+/// Shared between cyclomatic and cognitive complexity, which each fold a
+/// different per-token scoring rule over the same spans.
+#[derive(Debug, Clone)]
+pub(crate) struct FunctionSpan {
+    pub name: String,
+    pub line: usize,
+    pub body_start_idx: usize,
+    pub body_end_idx: usize,
 }
 
 impl CyclomaticMetrics {
@@ -36,68 +50,172 @@ impl CyclomaticMetrics {
     /// Decision points include: if, else if, while, for, loop, match/switch, case, catch, &&, ||, ?
     pub fn calculate(source: &str, language: Language) -> Result<Self> {
         let tokens = Tokenizer::new(source, language).tokenize()?;
-        let decision_points = tokens.iter().filter(|t| t.token_type.is_decision_point()).count();
+        let decision_points = tokens.iter().filter(|t| t.token_type.is_decision_point(language)).count();
         let file_complexity = if decision_points == 0 { 1 } else { decision_points + 1 };
         let functions = Self::detect_functions(&tokens, language);
 
         Ok(CyclomaticMetrics { file_complexity, functions })
     }
 
-    /// Attempt to detect function boundaries and calculate per-function complexity
+    /// Detect function boundaries (including nested functions/closures) and
+    /// calculate per-function complexity, with each nested function's
+    /// decision points subtracted from its enclosing function's count so a
+    /// closure or nested `fn` doesn't inflate the outer one.
+    fn detect_functions(tokens: &[Token<'_>], language: Language) -> Vec<FunctionComplexity> {
+        let spans = Self::detect_function_spans(tokens, language);
+
+        spans
+            .iter()
+            .map(|span| {
+                let own_points = Self::count_decision_points(tokens, span, language);
+                let nested_points: usize = Self::direct_children(span, &spans)
+                    .iter()
+                    .map(|child| Self::count_decision_points(tokens, child, language))
+                    .sum();
+                let decision_points = own_points.saturating_sub(nested_points);
+                let complexity = if decision_points == 0 { 1 } else { decision_points + 1 };
+                let end_line = tokens[span.body_end_idx].line;
+
+                FunctionComplexity { name: span.name.clone(), complexity, line: span.line, end_line }
+            })
+            .collect()
+    }
+
+    fn count_decision_points(tokens: &[Token<'_>], span: &FunctionSpan, language: Language) -> usize {
+        tokens[span.body_start_idx..=span.body_end_idx]
+            .iter()
+            .filter(|t| t.token_type.is_decision_point(language))
+            .count()
+    }
+
+    /// The spans strictly nested inside `parent` that aren't themselves
+    /// nested inside some other span also contained in `parent` — i.e. its
+    /// immediate children, not grandchildren, since a grandchild's range is
+    /// already excluded along with its direct parent's.
+    fn direct_children<'s>(parent: &FunctionSpan, all: &'s [FunctionSpan]) -> Vec<&'s FunctionSpan> {
+        let contained: Vec<&FunctionSpan> = all
+            .iter()
+            .filter(|s| !std::ptr::eq(*s, parent))
+            .filter(|s| s.body_start_idx > parent.body_start_idx && s.body_end_idx < parent.body_end_idx)
+            .collect();
+
+        contained
+            .iter()
+            .filter(|c| {
+                !contained
+                    .iter()
+                    .any(|other| other.body_start_idx < c.body_start_idx && other.body_end_idx > c.body_end_idx)
+            })
+            .copied()
+            .collect()
+    }
+
+    /// Detect function boundaries, yielding each one's name, starting line,
+    /// and the token-index span of its body. Nested functions/closures are
+    /// detected too (as their own spans), so the caller can build a nesting
+    /// tree rather than attribute their decision points to the enclosing
+    /// function.
     ///
     /// Look for function patterns:
     /// - Rust: "fn" identifier "(" ... ")" "{"
     /// - JS/TS: "function" identifier "(" ... ")" "{"
-    /// - Go: "func" identifier "(" ... ")" "{"
-    /// - Java/C++: type identifier "(" ... ")" "{"
-    fn detect_functions(tokens: &[Token], _language: Language) -> Vec<FunctionComplexity> {
-        let mut functions = Vec::new();
-        let mut i = 0;
-
-        while i < tokens.len() {
-            let is_function_keyword = if let TokenType::Identifier(name) = &tokens[i].token_type {
-                name == "fn" || name == "func" || name == "function"
-            } else {
-                false
-            };
-
-            if is_function_keyword {
-                let mut name = "anonymous".to_string();
-                let line = tokens[i].line;
-
-                if i + 1 < tokens.len()
-                    && let TokenType::Identifier(id) = &tokens[i + 1].token_type
-                {
-                    name = id.clone();
+    /// - Go: "func" identifier "(" ... ")" "{", or a method with a receiver:
+    ///   "func" "(" receiver ")" identifier "(" ... ")" "{"
+    /// - Java/C++: identifier "(" ... ")" "{" (the return type prefix, if
+    ///   any, is ignored)
+    pub(crate) fn detect_function_spans(tokens: &[Token<'_>], language: Language) -> Vec<FunctionSpan> {
+        let mut spans = Vec::new();
+
+        for i in 0..tokens.len() {
+            if let Some((name, line)) = Self::match_function_signature(tokens, i, language)
+                && let Some(body_start_idx) = Self::find_next_token(tokens, i, TokenType::LeftBrace)
+                && let Some(body_end_idx) = Self::find_matching_brace(tokens, body_start_idx)
+            {
+                spans.push(FunctionSpan { name, line, body_start_idx, body_end_idx });
+            }
+        }
+
+        spans
+    }
+
+    /// If `tokens[i]` begins a function/method signature, return its name
+    /// (or `"anonymous"` if unnamed) and starting line.
+    fn match_function_signature(tokens: &[Token<'_>], i: usize, language: Language) -> Option<(String, usize)> {
+        if let TokenType::Identifier(word) = &tokens[i].token_type {
+            let line = tokens[i].line;
+
+            match *word {
+                "fn" | "function" => {
+                    let name = Self::next_significant(tokens, i)
+                        .and_then(|idx| Self::identifier_name(tokens, idx))
+                        .unwrap_or_else(|| "anonymous".to_string());
+                    return Some((name, line));
+                }
+                "func" => {
+                    let after_keyword = Self::next_significant(tokens, i)?;
+
+                    if matches!(tokens[after_keyword].token_type, TokenType::LeftParen) {
+                        // A parenthesized group right after `func` is either a Go
+                        // method's receiver (in which case the method name follows
+                        // it) or the parameter list of an anonymous func literal.
+                        let close = Self::find_matching_paren(tokens, after_keyword)?;
+                        let name = Self::next_significant(tokens, close)
+                            .and_then(|idx| Self::identifier_name(tokens, idx))
+                            .unwrap_or_else(|| "anonymous".to_string());
+                        return Some((name, line));
+                    }
+
+                    let name = Self::identifier_name(tokens, after_keyword).unwrap_or_else(|| "anonymous".to_string());
+                    return Some((name, line));
                 }
+                _ => {}
+            }
+        }
 
-                let body_start = Self::find_next_token(tokens, i, TokenType::LeftBrace);
+        if matches!(language, Language::Java | Language::Cpp) {
+            return Self::match_method_signature(tokens, i);
+        }
 
-                if let Some(body_start_idx) = body_start
-                    && let Some(body_end_idx) = Self::find_matching_brace(tokens, body_start_idx)
-                {
-                    let decision_points = tokens[body_start_idx..=body_end_idx]
-                        .iter()
-                        .filter(|t| t.token_type.is_decision_point())
-                        .count();
+        None
+    }
 
-                    let complexity = if decision_points == 0 { 1 } else { decision_points + 1 };
+    /// Java/C++ have no function keyword, so a method looks like
+    /// `identifier "(" ... ")" "{"` with an optional return-type/modifier
+    /// prefix that this simply ignores.
+    fn match_method_signature(tokens: &[Token<'_>], i: usize) -> Option<(String, usize)> {
+        let TokenType::Identifier(name) = &tokens[i].token_type else {
+            return None;
+        };
+
+        let paren_idx = Self::next_significant(tokens, i)?;
+        if !matches!(tokens[paren_idx].token_type, TokenType::LeftParen) {
+            return None;
+        }
 
-                    functions.push(FunctionComplexity { name, complexity, line });
+        let close_idx = Self::find_matching_paren(tokens, paren_idx)?;
+        let brace_idx = Self::next_significant(tokens, close_idx)?;
+        if !matches!(tokens[brace_idx].token_type, TokenType::LeftBrace) {
+            return None;
+        }
 
-                    i = body_end_idx + 1;
-                    continue;
-                }
-            }
+        Some((name.to_string(), tokens[i].line))
+    }
 
-            i += 1;
+    fn identifier_name(tokens: &[Token<'_>], idx: usize) -> Option<String> {
+        match &tokens[idx].token_type {
+            TokenType::Identifier(name) => Some(name.to_string()),
+            _ => None,
         }
+    }
 
-        functions
+    /// The index of the next token after `from` that isn't whitespace,
+    /// a comment, or a newline.
+    fn next_significant(tokens: &[Token<'_>], from: usize) -> Option<usize> {
+        (from + 1..tokens.len()).find(|&idx| tokens[idx].token_type.is_significant())
     }
 
     /// Find the next token of a specific type
-    fn find_next_token(tokens: &[Token], start: usize, token_type: TokenType) -> Option<usize> {
+    fn find_next_token(tokens: &[Token<'_>], start: usize, token_type: TokenType<'_>) -> Option<usize> {
         tokens[start..]
             .iter()
             .position(|t| std::mem::discriminant(&t.token_type) == std::mem::discriminant(&token_type))
@@ -105,7 +223,7 @@ impl CyclomaticMetrics {
     }
 
     /// Find the matching closing brace for an opening brace
-    fn find_matching_brace(tokens: &[Token], open_idx: usize) -> Option<usize> {
+    fn find_matching_brace(tokens: &[Token<'_>], open_idx: usize) -> Option<usize> {
         let mut depth = 0;
 
         for (offset, token) in tokens[open_idx..].iter().enumerate() {
@@ -124,6 +242,26 @@ impl CyclomaticMetrics {
         None
     }
 
+    /// Find the matching closing paren for an opening paren
+    fn find_matching_paren(tokens: &[Token<'_>], open_idx: usize) -> Option<usize> {
+        let mut depth = 0;
+
+        for (offset, token) in tokens[open_idx..].iter().enumerate() {
+            match token.token_type {
+                TokenType::LeftParen => depth += 1,
+                TokenType::RightParen => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(open_idx + offset);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        None
+    }
+
     /// Get severity level based on complexity threshold
     /// Standard thresholds from literature:
     /// 1-10: Simple, low risk
@@ -284,4 +422,79 @@ if (x || y || z) { }
         let metrics = CyclomaticMetrics::calculate(source, Language::JavaScript).unwrap();
         assert_eq!(metrics.file_complexity, 7);
     }
+
+    #[test]
+    fn test_function_names_are_detected_not_anonymous() {
+        let source = r#"
+fn greet() {
+    println!("hi");
+}
+"#;
+        let metrics = CyclomaticMetrics::calculate(source, Language::Rust).unwrap();
+        assert_eq!(metrics.functions[0].name, "greet");
+    }
+
+    #[test]
+    fn test_nested_closure_does_not_inflate_enclosing_function() {
+        let source = r#"
+function outer() {
+    if (true) {
+        const inner = function helper() {
+            if (false) {
+                return 1;
+            }
+        };
+    }
+}
+"#;
+        let metrics = CyclomaticMetrics::calculate(source, Language::JavaScript).unwrap();
+        assert_eq!(metrics.functions.len(), 2);
+
+        let outer = metrics.functions.iter().find(|f| f.name == "outer").unwrap();
+        let inner = metrics.functions.iter().find(|f| f.name == "helper").unwrap();
+
+        // outer's own decision point is just its `if`; helper's nested `if`
+        // must not also count toward outer.
+        assert_eq!(outer.complexity, 2);
+        assert_eq!(inner.complexity, 2);
+    }
+
+    #[test]
+    fn test_go_receiver_method_is_named_from_method_not_receiver() {
+        let source = r#"
+func (r *Repo) Save(item string) {
+    if item != "" {
+        println(item)
+    }
+}
+"#;
+        let metrics = CyclomaticMetrics::calculate(source, Language::Go).unwrap();
+        assert_eq!(metrics.functions.len(), 1);
+        assert_eq!(metrics.functions[0].name, "Save");
+        assert_eq!(metrics.functions[0].complexity, 2);
+    }
+
+    #[test]
+    fn test_java_method_without_function_keyword_is_detected() {
+        let source = r#"
+public int add(int a, int b) {
+    if (a > b) {
+        return a;
+    }
+    return b;
+}
+"#;
+        let metrics = CyclomaticMetrics::calculate(source, Language::Java).unwrap();
+        assert_eq!(metrics.functions.len(), 1);
+        assert_eq!(metrics.functions[0].name, "add");
+        assert_eq!(metrics.functions[0].complexity, 2);
+    }
+
+    #[test]
+    fn test_end_line_reports_closing_brace() {
+        let source = "fn f() {\n    let x = 1;\n}\n";
+        let metrics = CyclomaticMetrics::calculate(source, Language::Rust).unwrap();
+        assert_eq!(metrics.functions[0].line, 1);
+        assert_eq!(metrics.functions[0].end_line, 3);
+    }
 }
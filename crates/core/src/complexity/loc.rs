@@ -1,11 +1,18 @@
 use crate::Result;
 use crate::tokenizer::{Language, TokenType, Tokenizer};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use siphasher::sip128::{Hash128, Hasher128, SipHasher13};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::hash::Hasher;
+use std::io::Read;
 use std::path::{Path, PathBuf};
 
+/// Size of the leading block read for the cheap partial-hash pre-filter
+const PARTIAL_HASH_BYTES: u64 = 4096;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum LineKind {
+pub(crate) enum LineKind {
     Code,
     Comment,
     Blank,
@@ -26,35 +33,8 @@ pub struct LocMetrics {
 
 impl LocMetrics {
     pub fn calculate(source: &str, language: Language) -> Result<Self> {
-        let tokens = Tokenizer::new(source, language).tokenize()?;
-        let physical = if source.is_empty() { 0 } else { source.split('\n').count() };
-        let mut line_types = vec![LineKind::Blank; physical];
-
-        for token in &tokens {
-            let line_idx = token.line.saturating_sub(1);
-            if line_idx >= line_types.len() {
-                continue;
-            }
-
-            match token.token_type {
-                _ if token.token_type.is_significant() => {
-                    line_types[line_idx] = LineKind::Code;
-                }
-                TokenType::Comment => {
-                    if line_types[line_idx] != LineKind::Code {
-                        line_types[line_idx] = LineKind::Comment;
-                    }
-                }
-                _ => {}
-            }
-        }
-
-        for (idx, line) in source.lines().enumerate() {
-            if line.trim().is_empty() && idx < line_types.len() {
-                line_types[idx] = LineKind::Blank;
-            }
-        }
-
+        let line_types = classify_lines(source, language)?;
+        let physical = line_types.len();
         let comments = line_types.iter().filter(|&&t| t == LineKind::Comment).count();
         let blank = line_types.iter().filter(|&&t| t == LineKind::Blank).count();
         let logical = physical - comments - blank;
@@ -73,6 +53,44 @@ impl LocMetrics {
     }
 }
 
+/// Classify each physical line of `source` as code, comment, or blank.
+///
+/// A line is `Code` if any significant token starts on it, `Comment` if only
+/// comment tokens start on it, and `Blank` otherwise. Code wins over comment
+/// when a line has both (e.g. a trailing inline comment after a statement).
+pub(crate) fn classify_lines(source: &str, language: Language) -> Result<Vec<LineKind>> {
+    let tokens = Tokenizer::new(source, language).tokenize()?;
+    let physical = if source.is_empty() { 0 } else { source.split('\n').count() };
+    let mut line_types = vec![LineKind::Blank; physical];
+
+    for token in &tokens {
+        let line_idx = token.line.saturating_sub(1);
+        if line_idx >= line_types.len() {
+            continue;
+        }
+
+        match token.token_type {
+            _ if token.token_type.is_significant() => {
+                line_types[line_idx] = LineKind::Code;
+            }
+            TokenType::Comment => {
+                if line_types[line_idx] != LineKind::Code {
+                    line_types[line_idx] = LineKind::Comment;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for (idx, line) in source.lines().enumerate() {
+        if line.trim().is_empty() && idx < line_types.len() {
+            line_types[idx] = LineKind::Blank;
+        }
+    }
+
+    Ok(line_types)
+}
+
 /// Ranking criteria for LOC analysis
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RankBy {
@@ -125,6 +143,8 @@ pub struct LocReport {
     pub files: Vec<FileLocReport>,
     /// Per-directory aggregation (if enabled)
     pub directories: Option<Vec<DirectoryLocMetrics>>,
+    /// Groups of files detected as byte-for-byte duplicates of one another
+    pub duplicates: Vec<Vec<PathBuf>>,
     /// Summary statistics
     pub summary: LocSummary,
 }
@@ -142,6 +162,8 @@ pub struct LocSummary {
     pub total_comments: usize,
     /// Total blank lines
     pub total_blank: usize,
+    /// Number of files that are duplicates of another file in the report
+    pub duplicate_files: usize,
 }
 
 impl LocReport {
@@ -150,9 +172,10 @@ impl LocReport {
         files.sort_by(|a, b| rank_by.value_from(&b.metrics).cmp(&rank_by.value_from(&a.metrics)));
 
         let directories = if rank_dirs { Some(Self::aggregate_by_directory(&files, rank_by)) } else { None };
-        let summary = LocSummary::from_files(&files);
+        let duplicates = find_duplicates(&files);
+        let summary = LocSummary::from_files(&files, &duplicates);
 
-        Self { files, directories, summary }
+        Self { files, directories, duplicates, summary }
     }
 
     /// Aggregate files by directory
@@ -191,15 +214,100 @@ impl LocReport {
 }
 
 impl LocSummary {
-    fn from_files(files: &[FileLocReport]) -> Self {
+    /// Sums LOC metrics across `files`, counting only one representative per
+    /// duplicate group so copy-pasted files don't inflate the totals.
+    fn from_files(files: &[FileLocReport], duplicates: &[Vec<PathBuf>]) -> Self {
         let total_files = files.len();
-        let total_physical = files.iter().map(|f| f.metrics.physical).sum();
-        let total_logical = files.iter().map(|f| f.metrics.logical).sum();
-        let total_comments = files.iter().map(|f| f.metrics.comments).sum();
-        let total_blank = files.iter().map(|f| f.metrics.blank).sum();
+        let duplicate_files = duplicates.iter().map(|group| group.len()).sum();
+
+        // `group[0]` still counts toward the totals; only the rest of each
+        // duplicate group is excluded.
+        let excess_duplicates: HashSet<&PathBuf> =
+            duplicates.iter().flat_map(|group| group.iter().skip(1)).collect();
+        let counted_files = files.iter().filter(|f| !excess_duplicates.contains(&f.path));
+
+        let total_physical = counted_files.clone().map(|f| f.metrics.physical).sum();
+        let total_logical = counted_files.clone().map(|f| f.metrics.logical).sum();
+        let total_comments = counted_files.clone().map(|f| f.metrics.comments).sum();
+        let total_blank = counted_files.map(|f| f.metrics.blank).sum();
+
+        Self { total_files, total_physical, total_logical, total_comments, total_blank, duplicate_files }
+    }
+}
+
+/// Detect duplicate files among `files` using a two-phase content hash.
+///
+/// Files are first bucketed by physical line count (a cheap, already-known
+/// pre-filter). Within a bucket, a partial hash over only the first
+/// [`PARTIAL_HASH_BYTES`] of each file groups likely matches together, and
+/// only files that collide there are fully read and hashed to confirm a true
+/// duplicate. Most files in a large tree never make it past the partial-hash
+/// stage, so they're never fully read.
+fn find_duplicates(files: &[FileLocReport]) -> Vec<Vec<PathBuf>> {
+    let mut by_size: HashMap<usize, Vec<&FileLocReport>> = HashMap::new();
+    for file in files {
+        by_size.entry(file.metrics.physical).or_default().push(file);
+    }
+
+    let mut groups = Vec::new();
+
+    for candidates in by_size.into_values() {
+        if candidates.len() < 2 {
+            continue;
+        }
+
+        let mut by_partial_hash: HashMap<u128, Vec<&FileLocReport>> = HashMap::new();
+        for file in candidates {
+            if let Ok(hash) = partial_hash(&file.path) {
+                by_partial_hash.entry(hash).or_default().push(file);
+            }
+        }
+
+        for partial_group in by_partial_hash.into_values() {
+            if partial_group.len() < 2 {
+                continue;
+            }
 
-        Self { total_files, total_physical, total_logical, total_comments, total_blank }
+            let mut by_full_hash: HashMap<u128, Vec<PathBuf>> = HashMap::new();
+            for file in partial_group {
+                if let Ok(hash) = full_hash(&file.path) {
+                    by_full_hash.entry(hash).or_default().push(file.path.clone());
+                }
+            }
+
+            for mut group in by_full_hash.into_values() {
+                if group.len() > 1 {
+                    group.sort();
+                    groups.push(group);
+                }
+            }
+        }
     }
+
+    groups.sort();
+    groups
+}
+
+/// Hash only the first [`PARTIAL_HASH_BYTES`] of a file
+fn partial_hash(path: &Path) -> std::io::Result<u128> {
+    let file = fs::File::open(path)?;
+    let mut buf = Vec::with_capacity(PARTIAL_HASH_BYTES as usize);
+    file.take(PARTIAL_HASH_BYTES).read_to_end(&mut buf)?;
+    Ok(hash_bytes(&buf))
+}
+
+/// Hash the entire contents of a file
+fn full_hash(path: &Path) -> std::io::Result<u128> {
+    let content = fs::read(path)?;
+    Ok(hash_bytes(&content))
+}
+
+/// Hash a byte slice with SipHash-1-3 (128-bit) to keep collisions negligible
+fn hash_bytes(bytes: &[u8]) -> u128 {
+    let mut hasher = SipHasher13::new();
+    hasher.write(bytes);
+    let Hash128 { h1, h2 } = hasher.finish128();
+    ((h1 as u128) << 64) | h2 as u128
 }
 
 #[cfg(test)]
@@ -371,4 +479,44 @@ function hello() {
         assert!(json.contains("summary"));
         assert!(json.contains("test.rs"));
     }
+
+    #[test]
+    fn test_find_duplicates_detects_identical_content() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let a = temp_dir.path().join("a.rs");
+        let b = temp_dir.path().join("b.rs");
+        let c = temp_dir.path().join("c.rs");
+
+        std::fs::write(&a, "fn shared() {}\n").unwrap();
+        std::fs::write(&b, "fn shared() {}\n").unwrap();
+        std::fs::write(&c, "fn different() {}\n").unwrap();
+
+        let files = vec![
+            FileLocReport { path: a.clone(), metrics: LocMetrics { physical: 1, logical: 1, comments: 0, blank: 0 } },
+            FileLocReport { path: b.clone(), metrics: LocMetrics { physical: 1, logical: 1, comments: 0, blank: 0 } },
+            FileLocReport { path: c, metrics: LocMetrics { physical: 1, logical: 1, comments: 0, blank: 0 } },
+        ];
+
+        let report = LocReport::new(files, RankBy::Logical, false);
+
+        assert_eq!(report.duplicates.len(), 1);
+        assert_eq!(report.duplicates[0], vec![a, b]);
+        assert_eq!(report.summary.duplicate_files, 2);
+        // `b`'s logical/physical LOC must not be double-counted alongside `a`'s.
+        assert_eq!(report.summary.total_physical, 2);
+        assert_eq!(report.summary.total_logical, 2);
+    }
+
+    #[test]
+    fn test_find_duplicates_no_false_positives_for_unique_files() {
+        let files = vec![FileLocReport {
+            path: PathBuf::from("only.rs"),
+            metrics: LocMetrics { physical: 10, logical: 8, comments: 1, blank: 1 },
+        }];
+
+        let report = LocReport::new(files, RankBy::Logical, false);
+
+        assert!(report.duplicates.is_empty());
+        assert_eq!(report.summary.duplicate_files, 0);
+    }
 }
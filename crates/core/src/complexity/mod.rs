@@ -1,5 +1,7 @@
+pub mod cognitive;
 pub mod cyclomatic;
 pub mod loc;
 
+pub use cognitive::{CognitiveMetrics, FunctionCognitive};
 pub use cyclomatic::{CyclomaticMetrics, FunctionComplexity, Severity};
 pub use loc::LocMetrics;
@@ -28,6 +28,33 @@ pub struct ComplexityConfig {
     /// Threshold for error level (default: 20)
     #[serde(default = "default_error_threshold")]
     pub error_threshold: usize,
+
+    /// Per-path/per-language threshold overrides, e.g. relaxing generated or
+    /// test code while keeping strict defaults everywhere else (default: none)
+    #[serde(default)]
+    pub overrides: Vec<ComplexityOverride>,
+}
+
+/// A single threshold override, scoped by glob pattern and/or language. Both
+/// `path` and `language` are optional; an override with neither set matches
+/// every file (and would normally just be the global threshold instead).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplexityOverride {
+    /// Glob pattern scoping this override, e.g. `tests/**` or `*.generated.rs`
+    #[serde(default)]
+    pub path: Option<String>,
+
+    /// Language name scoping this override, as accepted by [`Language::parse_name`]
+    #[serde(default)]
+    pub language: Option<String>,
+
+    /// Warning threshold to use instead of [`ComplexityConfig::warning_threshold`]
+    #[serde(default)]
+    pub warning_threshold: Option<usize>,
+
+    /// Error threshold to use instead of [`ComplexityConfig::error_threshold`]
+    #[serde(default)]
+    pub error_threshold: Option<usize>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,6 +66,16 @@ pub struct CloneConfig {
     /// Whether to enable clone detection (default: true)
     #[serde(default = "default_true")]
     pub enabled: bool,
+
+    /// Matching strictness, `"exact"` or `"normalized"` (default: "exact").
+    /// See [`crate::cloner::detector::DetectionMode`].
+    #[serde(default = "default_detection_mode")]
+    pub detection_mode: String,
+
+    /// Winnowing window, in k-grams, fingerprints are sampled from (default:
+    /// 1, i.e. every k-gram). See [`crate::cloner::detector::CloneDetector::with_winnow_window`].
+    #[serde(default = "default_winnow_window")]
+    pub winnow_window: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -46,23 +83,101 @@ pub struct FileConfig {
     /// Whether to respect .gitignore (default: true)
     #[serde(default = "default_true")]
     pub respect_gitignore: bool,
+
+    /// Whether to respect `.ignore`/`.mccabreignore` files (default: true)
+    #[serde(default = "default_true")]
+    pub respect_ignore_file: bool,
+
+    /// Glob patterns to scope analysis to, e.g. `src/**/*.rs` (default: none, analyze everything)
+    #[serde(default)]
+    pub include: Vec<String>,
+
+    /// Glob patterns to exclude from analysis, e.g. `**/tests/**` (default: none)
+    #[serde(default)]
+    pub exclude: Vec<String>,
+
+    /// Only analyze these languages, e.g. `["rust", "typescript"]` (default: none, allow all)
+    #[serde(default)]
+    pub languages: Vec<String>,
+
+    /// Exclude these languages from analysis, e.g. `["javascript"]` (default: none)
+    #[serde(default)]
+    pub languages_exclude: Vec<String>,
 }
 
 impl Default for ComplexityConfig {
     fn default() -> Self {
-        Self { warning_threshold: default_warning_threshold(), error_threshold: default_error_threshold() }
+        Self {
+            warning_threshold: default_warning_threshold(),
+            error_threshold: default_error_threshold(),
+            overrides: Vec::new(),
+        }
+    }
+}
+
+impl ComplexityConfig {
+    /// Resolve the warning/error thresholds that apply to `path` (optionally
+    /// known to be written in `language`), picking the most specific matching
+    /// override in `overrides` (one matching both path and language beats one
+    /// matching only either), with ties broken in favor of the override
+    /// listed last. Falls back to the global thresholds when nothing matches.
+    pub fn thresholds_for(&self, path: &Path, language: Option<crate::tokenizer::Language>) -> (usize, usize) {
+        let mut best: Option<(u8, &ComplexityOverride)> = None;
+
+        for candidate in &self.overrides {
+            let path_matches = match &candidate.path {
+                Some(pattern) => globset::Glob::new(pattern)
+                    .map(|glob| glob.compile_matcher().is_match(path))
+                    .unwrap_or(false),
+                None => true,
+            };
+
+            let language_matches = match &candidate.language {
+                Some(name) => crate::tokenizer::Language::parse_name(name) == language,
+                None => true,
+            };
+
+            if !path_matches || !language_matches {
+                continue;
+            }
+
+            let specificity = u8::from(candidate.path.is_some()) + u8::from(candidate.language.is_some());
+            if best.is_none_or(|(score, _)| specificity >= score) {
+                best = Some((specificity, candidate));
+            }
+        }
+
+        match best {
+            Some((_, candidate)) => (
+                candidate.warning_threshold.unwrap_or(self.warning_threshold),
+                candidate.error_threshold.unwrap_or(self.error_threshold),
+            ),
+            None => (self.warning_threshold, self.error_threshold),
+        }
     }
 }
 
 impl Default for CloneConfig {
     fn default() -> Self {
-        Self { min_tokens: default_min_tokens(), enabled: default_true() }
+        Self {
+            min_tokens: default_min_tokens(),
+            enabled: default_true(),
+            detection_mode: default_detection_mode(),
+            winnow_window: default_winnow_window(),
+        }
     }
 }
 
 impl Default for FileConfig {
     fn default() -> Self {
-        Self { respect_gitignore: default_true() }
+        Self {
+            respect_gitignore: default_true(),
+            respect_ignore_file: default_true(),
+            include: Vec::new(),
+            exclude: Vec::new(),
+            languages: Vec::new(),
+            languages_exclude: Vec::new(),
+        }
     }
 }
 
@@ -78,10 +193,150 @@ fn default_min_tokens() -> usize {
     30
 }
 
+fn default_detection_mode() -> String {
+    "exact".to_string()
+}
+
+fn default_winnow_window() -> usize {
+    1
+}
+
 fn default_true() -> bool {
     true
 }
 
+/// Read and parse an env var, if set. `Ok(None)` means the variable wasn't
+/// present; a value present but unparseable is an error rather than a silent
+/// skip, since a typo'd `MCCABRE_*` var should fail loudly, not be ignored.
+fn parse_env<T: std::str::FromStr>(name: &str) -> Result<Option<T>> {
+    match std::env::var(name) {
+        Ok(raw) => raw
+            .parse()
+            .map(Some)
+            .map_err(|_| MccabreError::InvalidConfig(format!("{name}={raw} is not valid"))),
+        Err(std::env::VarError::NotPresent) => Ok(None),
+        Err(std::env::VarError::NotUnicode(_)) => {
+            Err(MccabreError::InvalidConfig(format!("{name} is not valid UTF-8")))
+        }
+    }
+}
+
+/// A [`Config`] with every field optional, used while merging several config
+/// files discovered across a directory hierarchy. `None` means "not set by
+/// this layer", which is distinct from a field holding its default value.
+#[derive(Debug, Clone, Default, Deserialize)]
+struct PartialConfig {
+    #[serde(default)]
+    complexity: PartialComplexityConfig,
+    #[serde(default)]
+    clones: PartialCloneConfig,
+    #[serde(default)]
+    files: PartialFileConfig,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct PartialComplexityConfig {
+    warning_threshold: Option<usize>,
+    error_threshold: Option<usize>,
+    overrides: Option<Vec<ComplexityOverride>>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct PartialCloneConfig {
+    min_tokens: Option<usize>,
+    enabled: Option<bool>,
+    detection_mode: Option<String>,
+    winnow_window: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct PartialFileConfig {
+    respect_gitignore: Option<bool>,
+    respect_ignore_file: Option<bool>,
+    include: Option<Vec<String>>,
+    exclude: Option<Vec<String>>,
+    languages: Option<Vec<String>>,
+    languages_exclude: Option<Vec<String>>,
+}
+
+impl PartialConfig {
+    fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = fs::read_to_string(path.as_ref())
+            .map_err(|e| MccabreError::FileRead { path: path.as_ref().to_path_buf(), source: e })?;
+
+        toml::from_str(&content).map_err(|e| MccabreError::InvalidConfig(e.to_string()))
+    }
+
+    /// Fill any field still unset in `self` from `other`, so `self` (the
+    /// nearer/higher-precedence layer) wins field-by-field on conflicts.
+    fn fill_from(self, other: Self) -> Self {
+        Self {
+            complexity: self.complexity.fill_from(other.complexity),
+            clones: self.clones.fill_from(other.clones),
+            files: self.files.fill_from(other.files),
+        }
+    }
+
+    fn into_config(self) -> Config {
+        let defaults = Config::default();
+        Config {
+            complexity: ComplexityConfig {
+                warning_threshold: self.complexity.warning_threshold.unwrap_or(defaults.complexity.warning_threshold),
+                error_threshold: self.complexity.error_threshold.unwrap_or(defaults.complexity.error_threshold),
+                overrides: self.complexity.overrides.unwrap_or(defaults.complexity.overrides),
+            },
+            clones: CloneConfig {
+                min_tokens: self.clones.min_tokens.unwrap_or(defaults.clones.min_tokens),
+                enabled: self.clones.enabled.unwrap_or(defaults.clones.enabled),
+                detection_mode: self.clones.detection_mode.unwrap_or(defaults.clones.detection_mode),
+                winnow_window: self.clones.winnow_window.unwrap_or(defaults.clones.winnow_window),
+            },
+            files: FileConfig {
+                respect_gitignore: self.files.respect_gitignore.unwrap_or(defaults.files.respect_gitignore),
+                respect_ignore_file: self.files.respect_ignore_file.unwrap_or(defaults.files.respect_ignore_file),
+                include: self.files.include.unwrap_or(defaults.files.include),
+                exclude: self.files.exclude.unwrap_or(defaults.files.exclude),
+                languages: self.files.languages.unwrap_or(defaults.files.languages),
+                languages_exclude: self.files.languages_exclude.unwrap_or(defaults.files.languages_exclude),
+            },
+        }
+    }
+}
+
+impl PartialComplexityConfig {
+    fn fill_from(self, other: Self) -> Self {
+        Self {
+            warning_threshold: self.warning_threshold.or(other.warning_threshold),
+            error_threshold: self.error_threshold.or(other.error_threshold),
+            overrides: self.overrides.or(other.overrides),
+        }
+    }
+}
+
+impl PartialCloneConfig {
+    fn fill_from(self, other: Self) -> Self {
+        Self {
+            min_tokens: self.min_tokens.or(other.min_tokens),
+            enabled: self.enabled.or(other.enabled),
+            detection_mode: self.detection_mode.or(other.detection_mode),
+            winnow_window: self.winnow_window.or(other.winnow_window),
+        }
+    }
+}
+
+impl PartialFileConfig {
+    fn fill_from(self, other: Self) -> Self {
+        Self {
+            respect_gitignore: self.respect_gitignore.or(other.respect_gitignore),
+            respect_ignore_file: self.respect_ignore_file.or(other.respect_ignore_file),
+            include: self.include.or(other.include),
+            exclude: self.exclude.or(other.exclude),
+            languages: self.languages.or(other.languages),
+            languages_exclude: self.languages_exclude.or(other.languages_exclude),
+        }
+    }
+}
+
 impl Config {
     /// Load configuration from a TOML file
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
@@ -91,18 +346,49 @@ impl Config {
         toml::from_str(&content).map_err(|e| MccabreError::InvalidConfig(e.to_string()))
     }
 
-    /// Try to load configuration from default locations
-    /// Looks for: mccabre.toml, .mccabre.toml, .mccabre/config.toml
+    /// Try to load configuration from the current directory. See
+    /// [`Self::load_for_path`] for the discovery rules; this just anchors the
+    /// walk at `.` for callers with no specific analysis path in hand.
     pub fn load_default() -> Result<Self> {
-        let candidates = vec!["mccabre.toml", ".mccabre.toml", ".mccabre/config.toml"];
+        Self::load_for_path(".")
+    }
 
-        for path in candidates {
-            if Path::new(path).exists() {
-                return Self::from_file(path);
+    /// Cargo-style config discovery: starting from `path` (or its parent
+    /// directory, if `path` names a file), walk up each ancestor directory
+    /// looking for `mccabre.toml`, `.mccabre.toml`, or `.mccabre/config.toml`
+    /// (the first match in a given directory wins), then fall back to a
+    /// user-level `config.toml` under the OS config dir. Layers are merged
+    /// field-by-field with the one closest to `path` taking precedence, so a
+    /// monorepo can set shared thresholds at the root while a subproject
+    /// overrides just `complexity.warning_threshold` locally.
+    pub fn load_for_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        const CANDIDATE_FILENAMES: [&str; 3] = ["mccabre.toml", ".mccabre.toml", ".mccabre/config.toml"];
+
+        let absolute = path.as_ref().canonicalize().unwrap_or_else(|_| path.as_ref().to_path_buf());
+        let start_dir = if absolute.is_dir() { absolute.as_path() } else { absolute.parent().unwrap_or(&absolute) };
+
+        let mut layers: Vec<PartialConfig> = Vec::new();
+
+        for dir in start_dir.ancestors() {
+            if let Some(filename) = CANDIDATE_FILENAMES.iter().find(|name| dir.join(name).is_file()) {
+                layers.push(PartialConfig::from_file(dir.join(filename))?);
             }
         }
 
-        Ok(Self::default())
+        if let Some(user_config) = Self::user_config_path()
+            && user_config.is_file()
+        {
+            layers.push(PartialConfig::from_file(user_config)?);
+        }
+
+        let merged = layers.into_iter().fold(PartialConfig::default(), |acc, layer| acc.fill_from(layer));
+
+        Ok(merged.into_config())
+    }
+
+    /// The per-user config file, consulted as the last (weakest) layer.
+    fn user_config_path() -> Option<std::path::PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("mccabre").join("config.toml"))
     }
 
     /// Save configuration to a TOML file
@@ -115,9 +401,47 @@ impl Config {
         Ok(())
     }
 
+    /// Apply environment-variable overrides, sitting between file-based
+    /// config and CLI flags in precedence (file < env < CLI). Mirrors Cargo's
+    /// `CARGO_*` env layer, so CI jobs that can't easily drop a `mccabre.toml`
+    /// can still tune thresholds per job via the environment:
+    /// `MCCABRE_COMPLEXITY_WARNING_THRESHOLD`, `MCCABRE_COMPLEXITY_ERROR_THRESHOLD`,
+    /// `MCCABRE_CLONES_MIN_TOKENS`, `MCCABRE_FILES_RESPECT_GITIGNORE`,
+    /// `MCCABRE_CLONES_DETECTION_MODE`.
+    pub fn apply_env(mut self) -> Result<Self> {
+        if let Some(value) = parse_env("MCCABRE_COMPLEXITY_WARNING_THRESHOLD")? {
+            self.complexity.warning_threshold = value;
+        }
+
+        if let Some(value) = parse_env("MCCABRE_COMPLEXITY_ERROR_THRESHOLD")? {
+            self.complexity.error_threshold = value;
+        }
+
+        if let Some(value) = parse_env("MCCABRE_CLONES_MIN_TOKENS")? {
+            self.clones.min_tokens = value;
+        }
+
+        if let Some(value) = parse_env("MCCABRE_FILES_RESPECT_GITIGNORE")? {
+            self.files.respect_gitignore = value;
+        }
+
+        if let Some(value) = parse_env::<String>("MCCABRE_CLONES_DETECTION_MODE")? {
+            self.clones.detection_mode = value;
+        }
+
+        if let Some(value) = parse_env("MCCABRE_CLONES_WINNOW_WINDOW")? {
+            self.clones.winnow_window = value;
+        }
+
+        Ok(self)
+    }
+
     /// Merge with CLI overrides
     pub fn merge_with_cli(
         mut self, complexity_threshold: Option<usize>, min_tokens: Option<usize>, respect_gitignore: Option<bool>,
+        respect_ignore_file: Option<bool>, include: Option<Vec<String>>, exclude: Option<Vec<String>>,
+        languages: Option<Vec<String>>, languages_exclude: Option<Vec<String>>, detection_mode: Option<String>,
+        winnow_window: Option<usize>,
     ) -> Self {
         if let Some(threshold) = complexity_threshold {
             self.complexity.warning_threshold = threshold;
@@ -131,6 +455,34 @@ impl Config {
             self.files.respect_gitignore = respect;
         }
 
+        if let Some(respect) = respect_ignore_file {
+            self.files.respect_ignore_file = respect;
+        }
+
+        if let Some(include) = include {
+            self.files.include = include;
+        }
+
+        if let Some(exclude) = exclude {
+            self.files.exclude = exclude;
+        }
+
+        if let Some(languages) = languages {
+            self.files.languages = languages;
+        }
+
+        if let Some(languages_exclude) = languages_exclude {
+            self.files.languages_exclude = languages_exclude;
+        }
+
+        if let Some(detection_mode) = detection_mode {
+            self.clones.detection_mode = detection_mode;
+        }
+
+        if let Some(winnow_window) = winnow_window {
+            self.clones.winnow_window = winnow_window;
+        }
+
         self
     }
 }
@@ -148,6 +500,11 @@ mod tests {
         assert_eq!(config.clones.min_tokens, 30);
         assert!(config.clones.enabled);
         assert!(config.files.respect_gitignore);
+        assert!(config.files.respect_ignore_file);
+        assert!(config.files.include.is_empty());
+        assert!(config.files.exclude.is_empty());
+        assert!(config.files.languages.is_empty());
+        assert!(config.files.languages_exclude.is_empty());
     }
 
     #[test]
@@ -166,20 +523,151 @@ mod tests {
     #[test]
     fn test_merge_with_cli() {
         let mut config = Config::default();
-        config = config.merge_with_cli(Some(15), Some(40), Some(false));
+        config = config.merge_with_cli(
+            Some(15),
+            Some(40),
+            Some(false),
+            Some(false),
+            Some(vec!["src/**/*.rs".to_string()]),
+            Some(vec!["**/tests/**".to_string()]),
+            Some(vec!["rust".to_string()]),
+            Some(vec!["javascript".to_string()]),
+            Some("normalized".to_string()),
+            Some(4),
+        );
 
         assert_eq!(config.complexity.warning_threshold, 15);
         assert_eq!(config.clones.min_tokens, 40);
         assert!(!config.files.respect_gitignore);
+        assert!(!config.files.respect_ignore_file);
+        assert_eq!(config.files.include, vec!["src/**/*.rs".to_string()]);
+        assert_eq!(config.files.exclude, vec!["**/tests/**".to_string()]);
+        assert_eq!(config.files.languages, vec!["rust".to_string()]);
+        assert_eq!(config.files.languages_exclude, vec!["javascript".to_string()]);
+        assert_eq!(config.clones.detection_mode, "normalized");
+        assert_eq!(config.clones.winnow_window, 4);
     }
 
     #[test]
     fn test_partial_cli_override() {
         let mut config = Config::default();
-        config = config.merge_with_cli(Some(25), None, None);
+        config = config.merge_with_cli(Some(25), None, None, None, None, None, None, None, None, None);
 
         assert_eq!(config.complexity.warning_threshold, 25);
         assert_eq!(config.clones.min_tokens, 30);
         assert!(config.files.respect_gitignore);
+        assert!(config.files.respect_ignore_file);
+        assert!(config.files.include.is_empty());
+        assert!(config.files.exclude.is_empty());
+        assert!(config.files.languages.is_empty());
+        assert!(config.files.languages_exclude.is_empty());
+    }
+
+    #[test]
+    fn test_load_for_path_prefers_nearer_directory_over_parent() {
+        let temp_dir = TempDir::new().unwrap();
+        let child_dir = temp_dir.path().join("child");
+        fs::create_dir(&child_dir).unwrap();
+
+        fs::write(temp_dir.path().join("mccabre.toml"), "[complexity]\nwarning_threshold = 5\n").unwrap();
+        fs::write(child_dir.join("mccabre.toml"), "[complexity]\nwarning_threshold = 99\n").unwrap();
+
+        let config = Config::load_for_path(&child_dir).unwrap();
+        assert_eq!(config.complexity.warning_threshold, 99);
+    }
+
+    #[test]
+    fn test_load_for_path_merges_fields_across_ancestor_directories() {
+        let temp_dir = TempDir::new().unwrap();
+        let child_dir = temp_dir.path().join("child");
+        fs::create_dir(&child_dir).unwrap();
+
+        fs::write(temp_dir.path().join("mccabre.toml"), "[complexity]\nerror_threshold = 50\n").unwrap();
+        fs::write(child_dir.join("mccabre.toml"), "[complexity]\nwarning_threshold = 12\n").unwrap();
+
+        let config = Config::load_for_path(&child_dir).unwrap();
+        assert_eq!(config.complexity.warning_threshold, 12);
+        assert_eq!(config.complexity.error_threshold, 50);
+    }
+
+    #[test]
+    fn test_load_for_path_falls_back_to_defaults_when_unset() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = Config::load_for_path(temp_dir.path()).unwrap();
+        assert_eq!(config.complexity.warning_threshold, default_warning_threshold());
+        assert_eq!(config.clones.min_tokens, default_min_tokens());
+    }
+
+    #[test]
+    fn test_apply_env_overrides_matching_fields() {
+        // SAFETY: test-only, no other thread in this process reads these vars.
+        unsafe {
+            std::env::set_var("MCCABRE_COMPLEXITY_WARNING_THRESHOLD", "7");
+            std::env::set_var("MCCABRE_CLONES_MIN_TOKENS", "15");
+        }
+
+        let config = Config::default().apply_env().unwrap();
+
+        unsafe {
+            std::env::remove_var("MCCABRE_COMPLEXITY_WARNING_THRESHOLD");
+            std::env::remove_var("MCCABRE_CLONES_MIN_TOKENS");
+        }
+
+        assert_eq!(config.complexity.warning_threshold, 7);
+        assert_eq!(config.clones.min_tokens, 15);
+        assert_eq!(config.complexity.error_threshold, default_error_threshold());
+    }
+
+    #[test]
+    fn test_apply_env_rejects_unparseable_value() {
+        // SAFETY: test-only, no other thread in this process reads this var.
+        unsafe {
+            std::env::set_var("MCCABRE_COMPLEXITY_ERROR_THRESHOLD", "not-a-number");
+        }
+
+        let result = Config::default().apply_env();
+
+        unsafe {
+            std::env::remove_var("MCCABRE_COMPLEXITY_ERROR_THRESHOLD");
+        }
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_thresholds_for_prefers_most_specific_override() {
+        let mut config = ComplexityConfig::default();
+        config.overrides.push(ComplexityOverride {
+            path: Some("tests/**".to_string()),
+            language: None,
+            warning_threshold: Some(50),
+            error_threshold: None,
+        });
+        config.overrides.push(ComplexityOverride {
+            path: Some("tests/**".to_string()),
+            language: Some("rust".to_string()),
+            warning_threshold: Some(99),
+            error_threshold: None,
+        });
+
+        let (warning, error) =
+            config.thresholds_for(Path::new("tests/fixture.rs"), Some(crate::tokenizer::Language::Rust));
+        assert_eq!(warning, 99);
+        assert_eq!(error, default_error_threshold());
+    }
+
+    #[test]
+    fn test_thresholds_for_falls_back_to_global_when_nothing_matches() {
+        let mut config = ComplexityConfig::default();
+        config.overrides.push(ComplexityOverride {
+            path: Some("tests/**".to_string()),
+            language: None,
+            warning_threshold: Some(50),
+            error_threshold: None,
+        });
+
+        let (warning, error) = config.thresholds_for(Path::new("src/main.rs"), Some(crate::tokenizer::Language::Rust));
+        assert_eq!(warning, default_warning_threshold());
+        assert_eq!(error, default_error_threshold());
     }
 }
@@ -3,14 +3,31 @@ use std::collections::BTreeMap;
 use std::path::Path;
 
 pub fn parse_lcov_file(path: &Path, repo_root: Option<&Path>) -> Result<Vec<FileCoverage>> {
+    parse_lcov_file_sorted(path, repo_root, CoverageSortKey::LineRate)
+}
+
+pub fn parse_lcov_file_sorted(
+    path: &Path, repo_root: Option<&Path>, sort_key: CoverageSortKey,
+) -> Result<Vec<FileCoverage>> {
     let content = std::fs::read_to_string(path)
         .map_err(|e| MccabreError::Io(std::io::Error::other(format!("Failed to read LCOV file: {e}"))))?;
 
-    parse_lcov_content(&content, repo_root)
+    parse_lcov_content_sorted(&content, repo_root, sort_key)
 }
 
 pub fn parse_lcov_content(content: &str, repo_root: Option<&Path>) -> Result<Vec<FileCoverage>> {
+    parse_lcov_content_sorted(content, repo_root, CoverageSortKey::LineRate)
+}
+
+/// Parse an LCOV tracefile, ordering the resulting files by `sort_key`
+/// (ascending, worst-first) instead of always sorting by line rate.
+pub fn parse_lcov_content_sorted(
+    content: &str, repo_root: Option<&Path>, sort_key: CoverageSortKey,
+) -> Result<Vec<FileCoverage>> {
     let mut files: std::collections::HashMap<String, BTreeMap<u32, u64>> = std::collections::HashMap::new();
+    let mut branches: std::collections::HashMap<String, BTreeMap<(u32, u32, u32), Option<u64>>> =
+        std::collections::HashMap::new();
+    let mut functions: std::collections::HashMap<String, BTreeMap<String, u64>> = std::collections::HashMap::new();
     let mut current_file: Option<String> = None;
 
     for line in content.lines() {
@@ -24,6 +41,8 @@ pub fn parse_lcov_content(content: &str, repo_root: Option<&Path>) -> Result<Vec
             let path = super::paths::normalize_path(rest, repo_root);
             current_file = Some(path);
             files.entry(current_file.clone().unwrap()).or_default();
+            branches.entry(current_file.clone().unwrap()).or_default();
+            functions.entry(current_file.clone().unwrap()).or_default();
         } else if let Some(rest) = line.strip_prefix("DA:") {
             if let Some(ref file) = current_file
                 && let Some((line_num, count)) = rest.split_once(',')
@@ -31,6 +50,28 @@ pub fn parse_lcov_content(content: &str, repo_root: Option<&Path>) -> Result<Vec
             {
                 files.entry(file.clone()).or_default().insert(line_num, count);
             }
+        } else if let Some(rest) = line.strip_prefix("BRDA:") {
+            if let Some(ref file) = current_file
+                && let [line_num, block, branch, taken] = rest.splitn(4, ',').collect::<Vec<_>>()[..]
+                && let (Ok(line_num), Ok(block), Ok(branch)) =
+                    (line_num.parse::<u32>(), block.parse::<u32>(), branch.parse::<u32>())
+            {
+                let taken = if taken == "-" { None } else { taken.parse::<u64>().ok() };
+                branches.entry(file.clone()).or_default().insert((line_num, block, branch), taken);
+            }
+        } else if let Some(rest) = line.strip_prefix("FN:") {
+            if let Some(ref file) = current_file
+                && let Some((_line_num, name)) = rest.split_once(',')
+            {
+                functions.entry(file.clone()).or_default().entry(name.to_string()).or_insert(0);
+            }
+        } else if let Some(rest) = line.strip_prefix("FNDA:") {
+            if let Some(ref file) = current_file
+                && let Some((count, name)) = rest.split_once(',')
+                && let Ok(count) = count.parse::<u64>()
+            {
+                *functions.entry(file.clone()).or_default().entry(name.to_string()).or_insert(0) += count;
+            }
         } else if line == "end_of_record" {
             current_file = None;
         }
@@ -38,20 +79,21 @@ pub fn parse_lcov_content(content: &str, repo_root: Option<&Path>) -> Result<Vec
 
     let mut file_coverages = Vec::new();
     for (path, lines) in files {
-        file_coverages.push(FileCoverage::new(path, lines));
+        let branches = branches.remove(&path).unwrap_or_default();
+        let functions = functions.remove(&path).unwrap_or_default();
+        file_coverages.push(FileCoverage::with_details(path, lines, branches, functions));
     }
 
     file_coverages.sort_by(|a, b| {
-        a.summary
-            .rate
-            .partial_cmp(&b.summary.rate)
+        a.sort_key(sort_key)
+            .partial_cmp(&b.sort_key(sort_key))
             .unwrap_or(std::cmp::Ordering::Equal)
     });
 
     Ok(file_coverages)
 }
 
-use super::model::FileCoverage;
+use super::model::{CoverageSortKey, FileCoverage};
 
 #[cfg(test)]
 mod tests {
@@ -128,6 +170,107 @@ end_of_record
         assert_eq!(files[0].lines.get(&1), Some(&5));
     }
 
+    #[test]
+    fn test_parse_branch_records() {
+        let lcov = r#"SF:test.rs
+DA:1,10
+BRDA:1,0,0,10
+BRDA:1,0,1,0
+BRDA:1,0,2,-
+BRF:3
+BRH:1
+end_of_record
+"#;
+
+        let files = parse_lcov_content(lcov, None).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].branches.len(), 3);
+        assert_eq!(files[0].branches.get(&(1, 0, 0)), Some(&Some(10)));
+        assert_eq!(files[0].branches.get(&(1, 0, 1)), Some(&Some(0)));
+        assert_eq!(files[0].branches.get(&(1, 0, 2)), Some(&None));
+        assert_eq!(files[0].summary.branch_total, 3);
+        assert_eq!(files[0].summary.branch_hit, 1);
+    }
+
+    #[test]
+    fn test_parse_lcov_without_branches() {
+        let lcov = r#"SF:test.rs
+DA:1,10
+end_of_record
+"#;
+
+        let files = parse_lcov_content(lcov, None).unwrap();
+        assert!(files[0].branches.is_empty());
+        assert_eq!(files[0].summary.branch_total, 0);
+        assert_eq!(files[0].summary.branch_rate, None);
+    }
+
+    #[test]
+    fn test_parse_function_records() {
+        let lcov = r#"SF:test.rs
+DA:1,10
+FN:1,foo
+FN:5,bar
+FNDA:3,foo
+FNDA:0,bar
+FNF:2
+FNH:1
+end_of_record
+"#;
+
+        let files = parse_lcov_content(lcov, None).unwrap();
+        assert_eq!(files[0].functions.len(), 2);
+        assert_eq!(files[0].functions.get("foo"), Some(&3));
+        assert_eq!(files[0].functions.get("bar"), Some(&0));
+        assert_eq!(files[0].summary.function_total, 2);
+        assert_eq!(files[0].summary.function_hit, 1);
+        assert_eq!(files[0].summary.function_rate, Some(50.0));
+    }
+
+    #[test]
+    fn test_parse_function_records_sums_duplicate_fnda() {
+        let lcov = r#"SF:test.rs
+FN:1,foo
+FNDA:2,foo
+FNDA:3,foo
+end_of_record
+"#;
+
+        let files = parse_lcov_content(lcov, None).unwrap();
+        assert_eq!(files[0].functions.get("foo"), Some(&5));
+    }
+
+    #[test]
+    fn test_parse_lcov_without_functions_reports_na() {
+        let lcov = r#"SF:test.rs
+DA:1,10
+end_of_record
+"#;
+
+        let files = parse_lcov_content(lcov, None).unwrap();
+        assert!(files[0].functions.is_empty());
+        assert_eq!(files[0].summary.function_rate, None);
+    }
+
+    #[test]
+    fn test_parse_lcov_content_sorted_by_branch_rate() {
+        let lcov = r#"SF:high.rs
+BRDA:1,0,0,10
+end_of_record
+SF:low.rs
+BRDA:1,0,0,0
+end_of_record
+SF:no_branches.rs
+DA:1,10
+end_of_record
+"#;
+
+        let files = parse_lcov_content_sorted(lcov, None, CoverageSortKey::BranchRate).unwrap();
+        assert_eq!(files[0].path, "low.rs");
+        assert_eq!(files[1].path, "high.rs");
+        assert_eq!(files[2].path, "no_branches.rs");
+    }
+
     #[test]
     fn test_parse_sorted_by_coverage_rate() {
         let lcov = r#"SF:full.rs
@@ -2,23 +2,50 @@ pub mod lcov;
 pub mod misses;
 pub mod model;
 pub mod paths;
+pub mod sourcemap;
 
 pub use lcov::parse_lcov_content;
+pub use lcov::parse_lcov_content_sorted;
 pub use lcov::parse_lcov_file;
-pub use model::{CoverageReport, CoverageSummary, FileCoverage};
+pub use lcov::parse_lcov_file_sorted;
+pub use model::{BranchHit, CoverageReport, CoverageSortKey, CoverageSummary, FileCoverage};
+pub use paths::{VfsPath, normalize_path};
+pub use sourcemap::remap_through_source_maps;
 
 use crate::Result;
 
 pub fn parse_coverage_from_file(path: &std::path::Path, repo_root: Option<&std::path::Path>) -> Result<CoverageReport> {
-    let files = lcov::parse_lcov_file(path, repo_root)?;
+    parse_coverage_from_file_sorted(path, repo_root, CoverageSortKey::LineRate)
+}
+
+pub fn parse_coverage_from_file_sorted(
+    path: &std::path::Path, repo_root: Option<&std::path::Path>, sort_key: CoverageSortKey,
+) -> Result<CoverageReport> {
+    let files = lcov::parse_lcov_file_sorted(path, repo_root, sort_key)?;
     Ok(CoverageReport::new(files))
 }
 
 pub fn parse_coverage_from_content(content: &str, repo_root: Option<&std::path::Path>) -> Result<CoverageReport> {
-    let files = lcov::parse_lcov_content(content, repo_root)?;
+    parse_coverage_from_content_sorted(content, repo_root, CoverageSortKey::LineRate)
+}
+
+pub fn parse_coverage_from_content_sorted(
+    content: &str, repo_root: Option<&std::path::Path>, sort_key: CoverageSortKey,
+) -> Result<CoverageReport> {
+    let files = lcov::parse_lcov_content_sorted(content, repo_root, sort_key)?;
     Ok(CoverageReport::new(files))
 }
 
+/// Parse several LCOV files (e.g. one per test shard) and merge them into a
+/// single [`CoverageReport`], summing per-line and per-branch hit counts for
+/// any file that appears in more than one input.
+pub fn parse_and_merge(paths: &[std::path::PathBuf], repo_root: Option<&std::path::Path>) -> Result<CoverageReport> {
+    let reports: Vec<CoverageReport> =
+        paths.iter().map(|path| parse_coverage_from_file(path, repo_root)).collect::<Result<_>>()?;
+
+    Ok(CoverageReport::merge(reports))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -57,4 +84,50 @@ end_of_record
         assert_eq!(report.totals.hit, 3);
         assert_eq!(report.totals.miss, 1);
     }
+
+    #[test]
+    fn test_parse_and_merge_combines_shards() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let shard1 = dir.path().join("shard1.info");
+        std::fs::write(
+            &shard1,
+            "SF:test.rs\nDA:1,1\nDA:2,0\nend_of_record\n",
+        )
+        .unwrap();
+
+        let shard2 = dir.path().join("shard2.info");
+        std::fs::write(
+            &shard2,
+            "SF:test.rs\nDA:2,3\nDA:3,0\nend_of_record\n",
+        )
+        .unwrap();
+
+        let report = parse_and_merge(&[shard1, shard2], None).unwrap();
+        assert_eq!(report.files.len(), 1);
+
+        let file = &report.files[0];
+        assert_eq!(file.lines.get(&1), Some(&1));
+        assert_eq!(file.lines.get(&2), Some(&3));
+        assert_eq!(file.lines.get(&3), Some(&0));
+        assert_eq!(report.totals.total, 3);
+        assert_eq!(report.totals.hit, 2);
+    }
+
+    #[test]
+    fn test_parse_coverage_from_content_sorted_by_function_rate() {
+        let lcov = r#"SF:high.rs
+FN:1,foo
+FNDA:1,foo
+end_of_record
+SF:low.rs
+FN:1,bar
+FNDA:0,bar
+end_of_record
+"#;
+
+        let report = parse_coverage_from_content_sorted(lcov, None, CoverageSortKey::FunctionRate).unwrap();
+        assert_eq!(report.files[0].path, "low.rs");
+        assert_eq!(report.files[1].path, "high.rs");
+    }
 }
@@ -1,4 +1,5 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
 
 /// Coverage report for the entire codebase
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,6 +13,14 @@ impl CoverageReport {
         let totals = CoverageSummary::from_files(&files);
         Self { files, totals }
     }
+
+    /// Combine several reports (e.g. one per test shard) into one, unioning
+    /// each file's line/branch coverage across inputs: a line or branch is
+    /// covered if it was covered in any of them, with counts summed.
+    pub fn merge(reports: Vec<CoverageReport>) -> Self {
+        let files: Vec<FileCoverage> = reports.into_iter().flat_map(|r| r.files).collect();
+        Self::new(FileCoverage::merge_all(&files))
+    }
 }
 
 /// Coverage data for a single file
@@ -20,15 +29,153 @@ pub struct FileCoverage {
     pub path: String,
     pub lines: std::collections::BTreeMap<u32, u64>,
     pub miss_ranges: Vec<(u32, u32)>,
+    /// Branch coverage, keyed by `(line, block, branch)` as found in the
+    /// LCOV `BRDA` record. `None` means the branch was never reached (LCOV's
+    /// `-` marker); `Some(n)` is the hit count, which may be `0`.
+    #[serde(serialize_with = "serialize_branches", deserialize_with = "deserialize_branches")]
+    pub branches: BTreeMap<(u32, u32, u32), Option<u64>>,
+    /// Function coverage, keyed by the function name as found in the LCOV
+    /// `FN`/`FNDA` records, with hit counts summed across any duplicate
+    /// `FNDA` entries for the same name.
+    pub functions: BTreeMap<String, u64>,
     pub summary: CoverageSummary,
 }
 
 impl FileCoverage {
     pub fn new(path: String, lines: std::collections::BTreeMap<u32, u64>) -> Self {
-        let summary = CoverageSummary::from_lines(&lines);
+        Self::with_branches(path, lines, BTreeMap::new())
+    }
+
+    /// All recorded branches for a single line, e.g. to annotate a detailed
+    /// coverage view with "2/3 branches" for a line that executed but didn't
+    /// take every branch (which side of an `if`, `case`, or `&&`/`||`
+    /// short-circuit).
+    pub fn branches_for_line(&self, line: u32) -> Vec<BranchHit> {
+        self.branches
+            .iter()
+            .filter(|&(&(l, _, _), _)| l == line)
+            .map(|(&(_, block, branch), &taken)| BranchHit { block, branch, taken })
+            .collect()
+    }
+
+    pub fn with_branches(
+        path: String, lines: std::collections::BTreeMap<u32, u64>, branches: BTreeMap<(u32, u32, u32), Option<u64>>,
+    ) -> Self {
+        Self::with_details(path, lines, branches, BTreeMap::new())
+    }
+
+    pub fn with_details(
+        path: String, lines: std::collections::BTreeMap<u32, u64>, branches: BTreeMap<(u32, u32, u32), Option<u64>>,
+        functions: BTreeMap<String, u64>,
+    ) -> Self {
+        let summary = CoverageSummary::from_details(&lines, &branches, &functions);
         let miss_ranges = super::misses::compute_miss_ranges(&lines);
-        Self { path, lines, miss_ranges, summary }
+        Self { path, lines, miss_ranges, branches, functions, summary }
+    }
+
+    /// Union this file's coverage with another input for the same path
+    /// (e.g. from a different test shard): line, branch, and function hit
+    /// counts are summed, so a line/branch/function is covered if either
+    /// input covered it.
+    pub(crate) fn merge_with(&self, other: &FileCoverage) -> Self {
+        let mut lines = self.lines.clone();
+        for (&line, &count) in &other.lines {
+            *lines.entry(line).or_insert(0) += count;
+        }
+
+        let mut branches = self.branches.clone();
+        for (&key, &taken) in &other.branches {
+            let entry = branches.entry(key).or_insert(None);
+            *entry = match (*entry, taken) {
+                (Some(a), Some(b)) => Some(a + b),
+                (Some(a), None) => Some(a),
+                (None, taken) => taken,
+            };
+        }
+
+        let mut functions = self.functions.clone();
+        for (name, &count) in &other.functions {
+            *functions.entry(name.clone()).or_insert(0) += count;
+        }
+
+        FileCoverage::with_details(self.path.clone(), lines, branches, functions)
+    }
+
+    /// Merge `other`'s coverage into this file in place, for folding in a
+    /// second partial run one file at a time rather than rebuilding a whole
+    /// [`CoverageReport`]. See [`Self::merge_with`] for the merge semantics.
+    pub fn merge(&mut self, other: &FileCoverage) {
+        *self = self.merge_with(other);
+    }
+
+    /// Merge a flat list of per-file coverage (e.g. from several partial
+    /// shard reports) into one entry per path, summing line/branch/function
+    /// hit counts for any paths that recur. Used by [`CoverageReport::merge`]
+    /// and available directly for callers working with bare `FileCoverage`s.
+    pub fn merge_all(files: &[FileCoverage]) -> Vec<FileCoverage> {
+        let mut by_path: BTreeMap<String, FileCoverage> = BTreeMap::new();
+
+        for file in files {
+            by_path
+                .entry(file.path.clone())
+                .and_modify(|existing| *existing = existing.merge_with(file))
+                .or_insert_with(|| file.clone());
+        }
+
+        by_path.into_values().collect()
     }
+
+    /// The rate to sort this file by under `key`, with "not applicable"
+    /// rates (no branches/functions recorded) pushed to the end rather than
+    /// sorting as if they were `0%`.
+    pub fn sort_key(&self, key: CoverageSortKey) -> f64 {
+        match key {
+            CoverageSortKey::LineRate => self.summary.rate,
+            CoverageSortKey::BranchRate => self.summary.branch_rate.unwrap_or(f64::INFINITY),
+            CoverageSortKey::FunctionRate => self.summary.function_rate.unwrap_or(f64::INFINITY),
+        }
+    }
+}
+
+/// Which rate to sort a set of [`FileCoverage`] by, e.g. when ordering a
+/// parsed LCOV report worst-first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CoverageSortKey {
+    #[default]
+    LineRate,
+    BranchRate,
+    FunctionRate,
+}
+
+/// One branch decision recorded at a specific line (which side of an `if`,
+/// `case`, or `&&`/`||` short-circuit), as found in an LCOV `BRDA:` record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BranchHit {
+    pub block: u32,
+    pub branch: u32,
+    /// `None` means the branch was never reached (LCOV's `-` marker);
+    /// `Some(n)` is the hit count, which may be `0`.
+    pub taken: Option<u64>,
+}
+
+/// JSON object keys must be strings, so a composite `(line, block, branch)`
+/// key can't serialize as a map directly; round-trip it as a flat array of
+/// `[line, block, branch, taken]` tuples instead.
+fn serialize_branches<S>(branches: &BTreeMap<(u32, u32, u32), Option<u64>>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let entries: Vec<(u32, u32, u32, Option<u64>)> =
+        branches.iter().map(|(&(line, block, branch), &taken)| (line, block, branch, taken)).collect();
+    entries.serialize(serializer)
+}
+
+fn deserialize_branches<'de, D>(deserializer: D) -> Result<BTreeMap<(u32, u32, u32), Option<u64>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let entries = Vec::<(u32, u32, u32, Option<u64>)>::deserialize(deserializer)?;
+    Ok(entries.into_iter().map(|(line, block, branch, taken)| ((line, block, branch), taken)).collect())
 }
 
 /// Coverage summary statistics
@@ -38,16 +185,47 @@ pub struct CoverageSummary {
     pub hit: usize,
     pub miss: usize,
     pub rate: f64,
+    pub branch_total: usize,
+    pub branch_hit: usize,
+    /// `None` ("n/a") when the file has no branch records at all, distinct
+    /// from `Some(0.0)` when branches exist but none were taken.
+    pub branch_rate: Option<f64>,
+    pub function_total: usize,
+    pub function_hit: usize,
+    /// `None` ("n/a") when the file has no function records at all, distinct
+    /// from `Some(0.0)` when functions exist but none were called.
+    pub function_rate: Option<f64>,
 }
 
 impl CoverageSummary {
     pub fn from_lines(lines: &std::collections::BTreeMap<u32, u64>) -> Self {
+        Self::from_lines_and_branches(lines, &BTreeMap::new())
+    }
+
+    pub fn from_lines_and_branches(
+        lines: &std::collections::BTreeMap<u32, u64>, branches: &BTreeMap<(u32, u32, u32), Option<u64>>,
+    ) -> Self {
+        Self::from_details(lines, branches, &BTreeMap::new())
+    }
+
+    pub fn from_details(
+        lines: &std::collections::BTreeMap<u32, u64>, branches: &BTreeMap<(u32, u32, u32), Option<u64>>,
+        functions: &BTreeMap<String, u64>,
+    ) -> Self {
         let total = lines.len();
         let hit = lines.values().filter(|&&c| c > 0).count();
         let miss = lines.values().filter(|&&c| c == 0).count();
         let rate = if total > 0 { (hit as f64 / total as f64) * 100.0 } else { 0.0 };
 
-        Self { total, hit, miss, rate }
+        let branch_total = branches.len();
+        let branch_hit = branches.values().filter(|taken| matches!(taken, Some(n) if *n > 0)).count();
+        let branch_rate = (branch_total > 0).then(|| (branch_hit as f64 / branch_total as f64) * 100.0);
+
+        let function_total = functions.len();
+        let function_hit = functions.values().filter(|&&c| c > 0).count();
+        let function_rate = (function_total > 0).then(|| (function_hit as f64 / function_total as f64) * 100.0);
+
+        Self { total, hit, miss, rate, branch_total, branch_hit, branch_rate, function_total, function_hit, function_rate }
     }
 
     pub fn from_files(files: &[FileCoverage]) -> Self {
@@ -56,7 +234,15 @@ impl CoverageSummary {
         let miss: usize = files.iter().map(|f| f.summary.miss).sum();
         let rate = if total > 0 { (hit as f64 / total as f64) * 100.0 } else { 0.0 };
 
-        Self { total, hit, miss, rate }
+        let branch_total: usize = files.iter().map(|f| f.summary.branch_total).sum();
+        let branch_hit: usize = files.iter().map(|f| f.summary.branch_hit).sum();
+        let branch_rate = (branch_total > 0).then(|| (branch_hit as f64 / branch_total as f64) * 100.0);
+
+        let function_total: usize = files.iter().map(|f| f.summary.function_total).sum();
+        let function_hit: usize = files.iter().map(|f| f.summary.function_hit).sum();
+        let function_rate = (function_total > 0).then(|| (function_hit as f64 / function_total as f64) * 100.0);
+
+        Self { total, hit, miss, rate, branch_total, branch_hit, branch_rate, function_total, function_hit, function_rate }
     }
 }
 
@@ -115,5 +301,222 @@ mod tests {
         assert_eq!(file.summary.hit, 2);
         assert_eq!(file.summary.miss, 1);
         assert_eq!(file.miss_ranges, vec![(2, 2)]);
+        assert!(file.branches.is_empty());
+        assert_eq!(file.summary.branch_total, 0);
+        assert_eq!(file.summary.branch_rate, None);
+        assert!(file.functions.is_empty());
+        assert_eq!(file.summary.function_total, 0);
+        assert_eq!(file.summary.function_rate, None);
+    }
+
+    #[test]
+    fn test_coverage_summary_with_branches() {
+        let lines = std::collections::BTreeMap::new();
+        let mut branches = BTreeMap::new();
+        branches.insert((1, 0, 0), Some(5));
+        branches.insert((1, 0, 1), Some(0));
+        branches.insert((2, 0, 0), None);
+
+        let summary = CoverageSummary::from_lines_and_branches(&lines, &branches);
+        assert_eq!(summary.branch_total, 3);
+        assert_eq!(summary.branch_hit, 1);
+        assert!((summary.branch_rate.unwrap() - 33.33333333333333).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_coverage_summary_from_files_sums_branches() {
+        let mut lines1 = std::collections::BTreeMap::new();
+        lines1.insert(1, 10);
+        let mut branches1 = BTreeMap::new();
+        branches1.insert((1, 0, 0), Some(1));
+
+        let mut lines2 = std::collections::BTreeMap::new();
+        lines2.insert(1, 0);
+        let mut branches2 = BTreeMap::new();
+        branches2.insert((1, 0, 0), None);
+
+        let file1 = FileCoverage::with_branches("a.rs".to_string(), lines1, branches1);
+        let file2 = FileCoverage::with_branches("b.rs".to_string(), lines2, branches2);
+
+        let summary = CoverageSummary::from_files(&[file1, file2]);
+        assert_eq!(summary.branch_total, 2);
+        assert_eq!(summary.branch_hit, 1);
+        assert_eq!(summary.branch_rate, Some(50.0));
+    }
+
+    #[test]
+    fn test_merge_sums_line_counts_for_shared_files() {
+        let mut lines1 = std::collections::BTreeMap::new();
+        lines1.insert(1, 1);
+        lines1.insert(2, 0);
+
+        let mut lines2 = std::collections::BTreeMap::new();
+        lines2.insert(2, 3);
+        lines2.insert(3, 0);
+
+        let report1 = CoverageReport::new(vec![FileCoverage::new("a.rs".to_string(), lines1)]);
+        let report2 = CoverageReport::new(vec![FileCoverage::new("a.rs".to_string(), lines2)]);
+
+        let merged = CoverageReport::merge(vec![report1, report2]);
+        assert_eq!(merged.files.len(), 1);
+
+        let file = &merged.files[0];
+        assert_eq!(file.lines.get(&1), Some(&1));
+        assert_eq!(file.lines.get(&2), Some(&3));
+        assert_eq!(file.lines.get(&3), Some(&0));
+        assert_eq!(file.summary.total, 3);
+        assert_eq!(file.summary.hit, 2);
+        assert_eq!(file.summary.miss, 1);
+        assert_eq!(file.miss_ranges, vec![(3, 3)]);
+    }
+
+    #[test]
+    fn test_merge_keeps_files_unique_to_one_input() {
+        let mut lines1 = std::collections::BTreeMap::new();
+        lines1.insert(1, 1);
+        let mut lines2 = std::collections::BTreeMap::new();
+        lines2.insert(1, 1);
+
+        let report1 = CoverageReport::new(vec![FileCoverage::new("a.rs".to_string(), lines1)]);
+        let report2 = CoverageReport::new(vec![FileCoverage::new("b.rs".to_string(), lines2)]);
+
+        let merged = CoverageReport::merge(vec![report1, report2]);
+        let paths: Vec<&str> = merged.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["a.rs", "b.rs"]);
+        assert_eq!(merged.totals.total, 2);
+        assert_eq!(merged.totals.hit, 2);
+    }
+
+    #[test]
+    fn test_merge_combines_branch_counts() {
+        let mut branches1 = BTreeMap::new();
+        branches1.insert((1, 0, 0), Some(1));
+        branches1.insert((2, 0, 0), None);
+
+        let mut branches2 = BTreeMap::new();
+        branches2.insert((1, 0, 0), Some(2));
+        branches2.insert((2, 0, 0), Some(4));
+
+        let report1 = CoverageReport::new(vec![FileCoverage::with_branches(
+            "a.rs".to_string(),
+            std::collections::BTreeMap::new(),
+            branches1,
+        )]);
+        let report2 = CoverageReport::new(vec![FileCoverage::with_branches(
+            "a.rs".to_string(),
+            std::collections::BTreeMap::new(),
+            branches2,
+        )]);
+
+        let merged = CoverageReport::merge(vec![report1, report2]);
+        let file = &merged.files[0];
+        assert_eq!(file.branches.get(&(1, 0, 0)), Some(&Some(3)));
+        assert_eq!(file.branches.get(&(2, 0, 0)), Some(&Some(4)));
+    }
+
+    #[test]
+    fn test_merge_sums_function_hit_counts() {
+        let mut functions1 = BTreeMap::new();
+        functions1.insert("foo".to_string(), 1);
+
+        let mut functions2 = BTreeMap::new();
+        functions2.insert("foo".to_string(), 2);
+        functions2.insert("bar".to_string(), 0);
+
+        let report1 = CoverageReport::new(vec![FileCoverage::with_details(
+            "a.rs".to_string(),
+            std::collections::BTreeMap::new(),
+            BTreeMap::new(),
+            functions1,
+        )]);
+        let report2 = CoverageReport::new(vec![FileCoverage::with_details(
+            "a.rs".to_string(),
+            std::collections::BTreeMap::new(),
+            BTreeMap::new(),
+            functions2,
+        )]);
+
+        let merged = CoverageReport::merge(vec![report1, report2]);
+        let file = &merged.files[0];
+        assert_eq!(file.functions.get("foo"), Some(&3));
+        assert_eq!(file.functions.get("bar"), Some(&0));
+        assert_eq!(file.summary.function_total, 2);
+        assert_eq!(file.summary.function_hit, 1);
+    }
+
+    #[test]
+    fn test_branches_for_line_filters_and_projects_by_line() {
+        let mut branches = BTreeMap::new();
+        branches.insert((1, 0, 0), Some(5));
+        branches.insert((1, 0, 1), Some(0));
+        branches.insert((2, 0, 0), None);
+
+        let file =
+            FileCoverage::with_branches("a.rs".to_string(), std::collections::BTreeMap::new(), branches);
+
+        let line_1 = file.branches_for_line(1);
+        assert_eq!(line_1.len(), 2);
+        assert!(line_1.contains(&BranchHit { block: 0, branch: 0, taken: Some(5) }));
+        assert!(line_1.contains(&BranchHit { block: 0, branch: 1, taken: Some(0) }));
+
+        let line_2 = file.branches_for_line(2);
+        assert_eq!(line_2, vec![BranchHit { block: 0, branch: 0, taken: None }]);
+
+        assert!(file.branches_for_line(3).is_empty());
+    }
+
+    #[test]
+    fn test_file_coverage_merge_in_place_sums_lines_and_branches() {
+        let mut lines1 = std::collections::BTreeMap::new();
+        lines1.insert(1, 1);
+        lines1.insert(2, 0);
+        let mut branches1 = BTreeMap::new();
+        branches1.insert((1, 0, 0), Some(1));
+
+        let mut file = FileCoverage::with_branches("a.rs".to_string(), lines1, branches1);
+
+        let mut lines2 = std::collections::BTreeMap::new();
+        lines2.insert(2, 3);
+        let mut branches2 = BTreeMap::new();
+        branches2.insert((1, 0, 0), Some(2));
+
+        let other = FileCoverage::with_branches("a.rs".to_string(), lines2, branches2);
+
+        file.merge(&other);
+
+        assert_eq!(file.lines.get(&2), Some(&3));
+        assert_eq!(file.branches.get(&(1, 0, 0)), Some(&Some(3)));
+        assert_eq!(file.summary.hit, 2);
+    }
+
+    #[test]
+    fn test_merge_all_collapses_shared_paths_and_keeps_unique_ones() {
+        let mut lines1 = std::collections::BTreeMap::new();
+        lines1.insert(1, 1);
+        let mut lines2 = std::collections::BTreeMap::new();
+        lines2.insert(1, 0);
+        let mut lines3 = std::collections::BTreeMap::new();
+        lines3.insert(1, 1);
+
+        let files = vec![
+            FileCoverage::new("a.rs".to_string(), lines1),
+            FileCoverage::new("a.rs".to_string(), lines2),
+            FileCoverage::new("b.rs".to_string(), lines3),
+        ];
+
+        let merged = FileCoverage::merge_all(&files);
+        let paths: Vec<&str> = merged.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["a.rs", "b.rs"]);
+
+        let a = merged.iter().find(|f| f.path == "a.rs").unwrap();
+        assert_eq!(a.lines.get(&1), Some(&1));
+    }
+
+    #[test]
+    fn test_sort_key_treats_missing_branch_and_function_rate_as_not_applicable() {
+        let file = FileCoverage::new("a.rs".to_string(), std::collections::BTreeMap::new());
+        assert_eq!(file.sort_key(CoverageSortKey::LineRate), 0.0);
+        assert_eq!(file.sort_key(CoverageSortKey::BranchRate), f64::INFINITY);
+        assert_eq!(file.sort_key(CoverageSortKey::FunctionRate), f64::INFINITY);
     }
 }
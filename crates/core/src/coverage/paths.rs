@@ -1,19 +1,170 @@
 use std::path::{Path, PathBuf};
 
-pub fn normalize_path(path: &str, repo_root: Option<&Path>) -> String {
-    let path = PathBuf::from(path);
+/// A normalized virtual path, used to correlate coverage records (LCOV `SF:`
+/// paths) with discovered source files regardless of platform separators,
+/// redundant `.`/`..` segments, or trailing slashes.
+///
+/// Segments are always resolved: `.` is dropped, `..` pops the previous
+/// segment (or, once the anchor is exhausted, increments `supers` so the
+/// path can still round-trip as `../../something`). The canonical string form
+/// never has a trailing `/`, never has `//`, and is stable across platforms.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct VfsPath {
+    segments: Vec<String>,
+    /// Number of `..` components that walked past the anchor
+    supers: usize,
+    absolute: bool,
+}
 
-    let normalized = if let Some(root) = repo_root {
-        if let Ok(stripped) = path.strip_prefix(root) {
-            if stripped.as_os_str().is_empty() { PathBuf::from(".") } else { stripped.to_path_buf() }
-        } else {
-            path
+impl VfsPath {
+    /// The empty relative path (".")
+    pub fn empty() -> Self {
+        Self { segments: Vec::new(), supers: 0, absolute: false }
+    }
+
+    /// Parse a path string, accepting both `/` and `\` separators and
+    /// folding away `.`/`..` components. This never fails: malformed input
+    /// (empty segments, redundant separators) is simply dropped.
+    pub fn parse(input: &str) -> Self {
+        let normalized = input.replace('\\', "/");
+        let absolute = normalized.starts_with('/');
+        let mut path = Self { segments: Vec::new(), supers: 0, absolute };
+
+        for part in normalized.split('/') {
+            if !part.is_empty() {
+                // `push_segment` only rejects segments containing a
+                // separator, which split('/') can never produce.
+                path.push_segment(part).expect("split('/') cannot yield a segment containing '/'");
+            }
         }
-    } else {
+
         path
+    }
+
+    /// Append a relative path string, resolving any `.`/`..` it contains
+    /// against this path. An absolute `relative` replaces `self` entirely.
+    pub fn push(&mut self, relative: &str) {
+        let other = Self::parse(relative);
+
+        if other.absolute {
+            *self = other;
+            return;
+        }
+
+        for _ in 0..other.supers {
+            self.pop_or_else_super();
+        }
+        self.segments.extend(other.segments);
+    }
+
+    /// Append a single path segment, rejecting anything that itself contains
+    /// a separator (i.e. this is not a general path parser).
+    pub fn push_segment(&mut self, segment: &str) -> Result<(), String> {
+        if segment.is_empty() {
+            return Err("path segment must not be empty".to_string());
+        }
+        if segment.contains(['/', '\\']) {
+            return Err(format!("path segment must not contain a separator: {segment:?}"));
+        }
+
+        match segment {
+            "." => {}
+            ".." => self.pop_or_else_super(),
+            seg => self.segments.push(seg.to_string()),
+        }
+
+        Ok(())
+    }
+
+    /// Remove the last segment. Returns `false` if there was nothing to pop.
+    pub fn pop(&mut self) -> bool {
+        self.segments.pop().is_some()
+    }
+
+    fn pop_or_else_super(&mut self) {
+        if self.segments.pop().is_none() && !self.absolute {
+            self.supers += 1;
+        }
+    }
+
+    pub fn is_absolute(&self) -> bool {
+        self.absolute
+    }
+
+    pub fn segments(&self) -> &[String] {
+        &self.segments
+    }
+
+    /// Render the canonical string form: no trailing `/`, no `//`, `.`/`..`
+    /// already resolved.
+    pub fn to_canonical_string(&self) -> String {
+        let mut out = String::new();
+
+        if self.absolute {
+            out.push('/');
+        } else {
+            for _ in 0..self.supers {
+                out.push_str("../");
+            }
+        }
+
+        out.push_str(&self.segments.join("/"));
+
+        if out.len() > 1 && out.ends_with('/') {
+            out.pop();
+        }
+
+        out
+    }
+
+    /// Relativize `self` against `root`, returning `None` if `self` is not
+    /// rooted at `root`.
+    pub fn strip_prefix(&self, root: &VfsPath) -> Option<VfsPath> {
+        if self.absolute != root.absolute || self.segments.len() < root.segments.len() {
+            return None;
+        }
+        if self.segments[..root.segments.len()] != root.segments[..] {
+            return None;
+        }
+
+        Some(VfsPath { segments: self.segments[root.segments.len()..].to_vec(), supers: 0, absolute: false })
+    }
+
+    pub fn to_path_buf(&self) -> PathBuf {
+        PathBuf::from(self.to_canonical_string())
+    }
+}
+
+impl From<&Path> for VfsPath {
+    fn from(path: &Path) -> Self {
+        VfsPath::parse(&path.to_string_lossy())
+    }
+}
+
+impl std::fmt::Display for VfsPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_canonical_string())
+    }
+}
+
+/// Normalize an LCOV `SF:` path, relativizing it against `repo_root` when
+/// provided. Returns `"."` when the path and root are identical.
+pub fn normalize_path(path: &str, repo_root: Option<&Path>) -> String {
+    let vfs = VfsPath::parse(path);
+
+    let relative = match repo_root {
+        Some(root) => {
+            let root_vfs = VfsPath::from(root);
+            vfs.strip_prefix(&root_vfs).unwrap_or(vfs)
+        }
+        None => vfs,
     };
 
-    normalized.display().to_string()
+    if relative.segments().is_empty() && !relative.is_absolute() {
+        ".".to_string()
+    } else {
+        relative.to_canonical_string()
+    }
 }
 
 #[cfg(test)]
@@ -57,4 +208,49 @@ mod tests {
         let normalized = normalize_path(path, Some(root));
         assert_eq!(normalized, ".");
     }
+
+    #[test]
+    fn test_vfs_path_folds_dot_and_dotdot() {
+        let vfs = VfsPath::parse("/repo/./src/../src/lib.rs");
+        assert_eq!(vfs.to_canonical_string(), "/repo/src/lib.rs");
+    }
+
+    #[test]
+    fn test_vfs_path_collapses_redundant_separators() {
+        let vfs = VfsPath::parse("repo//src///lib.rs/");
+        assert_eq!(vfs.to_canonical_string(), "repo/src/lib.rs");
+    }
+
+    #[test]
+    fn test_vfs_path_tracks_supers_past_anchor() {
+        let vfs = VfsPath::parse("../../src/lib.rs");
+        assert_eq!(vfs.to_canonical_string(), "../../src/lib.rs");
+    }
+
+    #[test]
+    fn test_vfs_path_push_segment_rejects_separator() {
+        let mut vfs = VfsPath::empty();
+        assert!(vfs.push_segment("a/b").is_err());
+        assert!(vfs.push_segment("a").is_ok());
+    }
+
+    #[test]
+    fn test_vfs_path_push_relative() {
+        let mut vfs = VfsPath::parse("/repo/src");
+        vfs.push("../lib.rs");
+        assert_eq!(vfs.to_canonical_string(), "/repo/lib.rs");
+    }
+
+    #[test]
+    fn test_vfs_path_pop() {
+        let mut vfs = VfsPath::parse("/repo/src/lib.rs");
+        assert!(vfs.pop());
+        assert_eq!(vfs.to_canonical_string(), "/repo/src");
+    }
+
+    #[test]
+    fn test_vfs_path_handles_windows_separators() {
+        let vfs = VfsPath::parse(r"repo\src\lib.rs");
+        assert_eq!(vfs.to_canonical_string(), "repo/src/lib.rs");
+    }
 }
@@ -0,0 +1,116 @@
+use super::model::{CoverageReport, FileCoverage};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// The sibling map file a bundler conventionally emits next to `file_path`,
+/// e.g. `dist/app.js` -> `dist/app.js.map`.
+fn sibling_map_path(file_path: &Path) -> PathBuf {
+    let mut name = file_path.as_os_str().to_os_string();
+    name.push(".map");
+    PathBuf::from(name)
+}
+
+/// The map file named by a trailing `//# sourceMappingURL=` comment, if any.
+/// Data-URI inline maps are not supported, only file references.
+fn inline_map_url(source: &str) -> Option<&str> {
+    source.lines().rev().find_map(|line| line.trim().strip_prefix("//# sourceMappingURL="))
+}
+
+fn load_source_map(file_path: &Path) -> Option<sourcemap::SourceMap> {
+    if let Ok(bytes) = std::fs::read(sibling_map_path(file_path)) {
+        return sourcemap::SourceMap::from_reader(bytes.as_slice()).ok();
+    }
+
+    let source = std::fs::read_to_string(file_path).ok()?;
+    let url = inline_map_url(&source)?;
+    let map_path = file_path.parent().unwrap_or_else(|| Path::new(".")).join(url);
+    let bytes = std::fs::read(map_path).ok()?;
+    sourcemap::SourceMap::from_reader(bytes.as_slice()).ok()
+}
+
+fn merge_into(by_path: &mut BTreeMap<String, FileCoverage>, file: FileCoverage) {
+    match by_path.get(&file.path) {
+        Some(existing) => {
+            let merged = existing.merge_with(&file);
+            by_path.insert(merged.path.clone(), merged);
+        }
+        None => {
+            by_path.insert(file.path.clone(), file);
+        }
+    }
+}
+
+/// Remap each file's line coverage through its source map (a sibling
+/// `.map` file, or a `//# sourceMappingURL=` comment) back to the original
+/// sources a developer wrote, so coverage on bundled/transpiled output is
+/// attributed to the files a developer actually edits. Emitted lines with no
+/// matching token, and files with no source map at all, pass through
+/// unchanged. When several emitted lines map to the same original line
+/// (common after minification), their counts are summed. Branch coverage is
+/// not remapped and is dropped for any file that is remapped, since LCOV's
+/// `BRDA` columns don't carry enough positional detail to resolve reliably
+/// through a source map.
+pub fn remap_through_source_maps(report: &CoverageReport, repo_root: Option<&Path>) -> CoverageReport {
+    let mut by_path: BTreeMap<String, FileCoverage> = BTreeMap::new();
+
+    for file in &report.files {
+        let resolved = match repo_root {
+            Some(root) => root.join(&file.path),
+            None => Path::new(&file.path).to_path_buf(),
+        };
+
+        let Some(sm) = load_source_map(&resolved) else {
+            merge_into(&mut by_path, file.clone());
+            continue;
+        };
+
+        let mut remapped: BTreeMap<String, BTreeMap<u32, u64>> = BTreeMap::new();
+
+        for (&line, &count) in &file.lines {
+            let (src_path, src_line) = match sm.lookup_token(line.saturating_sub(1), 0) {
+                Some(token) => (token.get_source().unwrap_or(&file.path).to_string(), token.get_src_line() + 1),
+                None => (file.path.clone(), line),
+            };
+            *remapped.entry(src_path).or_default().entry(src_line).or_insert(0) += count;
+        }
+
+        for (src_path, lines) in remapped {
+            merge_into(&mut by_path, FileCoverage::new(src_path, lines));
+        }
+    }
+
+    CoverageReport::new(by_path.into_values().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sibling_map_path_appends_extension() {
+        assert_eq!(sibling_map_path(Path::new("dist/app.js")), PathBuf::from("dist/app.js.map"));
+    }
+
+    #[test]
+    fn test_inline_map_url_finds_trailing_comment() {
+        let source = "console.log(1);\n//# sourceMappingURL=app.js.map\n";
+        assert_eq!(inline_map_url(source), Some("app.js.map"));
+    }
+
+    #[test]
+    fn test_inline_map_url_absent() {
+        let source = "console.log(1);\n";
+        assert_eq!(inline_map_url(source), None);
+    }
+
+    #[test]
+    fn test_remap_passes_through_files_without_a_map() {
+        let mut lines = BTreeMap::new();
+        lines.insert(1, 5);
+        let report = CoverageReport::new(vec![FileCoverage::new("dist/app.js".to_string(), lines)]);
+
+        let remapped = remap_through_source_maps(&report, None);
+        assert_eq!(remapped.files.len(), 1);
+        assert_eq!(remapped.files[0].path, "dist/app.js");
+    }
+}
@@ -18,6 +18,12 @@ pub enum MccabreError {
 
     #[error("IO error: {0}")]
     Io(#[from] io::Error),
+
+    #[error("Invalid glob pattern `{pattern}`: {source}")]
+    Glob { pattern: String, source: globset::Error },
+
+    #[error("Git command failed: {0}")]
+    Git(String),
 }
 
 pub type Result<T> = std::result::Result<T, MccabreError>;
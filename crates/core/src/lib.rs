@@ -1,6 +1,9 @@
 pub mod cloner;
+pub mod combined;
+pub mod compare;
 pub mod complexity;
 pub mod config;
+pub mod coverage;
 pub mod error;
 pub mod loader;
 pub mod reporter;
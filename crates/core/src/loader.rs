@@ -1,7 +1,11 @@
 use crate::error::{MccabreError, Result};
 use crate::tokenizer::Language;
-use ignore::WalkBuilder;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::{WalkBuilder, WalkState};
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{mpsc, Arc, Mutex};
 use std::{fs, io};
 
 /// File entry with source code and metadata
@@ -12,15 +16,56 @@ pub struct SourceFile {
     pub language: Language,
 }
 
+/// Name of the VCS-independent ignore file consulted regardless of whether
+/// the project is a git repository, alongside the generic `.ignore` file the
+/// `ignore` crate already honors.
+const CUSTOM_IGNORE_FILENAME: &str = ".mccabreignore";
+
+/// Which git changes [`FileLoader::load_changed`] should scope a load to
+#[derive(Debug, Clone)]
+pub enum ChangeScope {
+    /// Files changed (added/copied/modified/renamed) relative to `rev`,
+    /// including uncommitted working-tree changes
+    Since(String),
+    /// Files staged in the index
+    Staged,
+}
+
 /// File loader that respects .gitignore and supports various input types
 pub struct FileLoader {
-    /// Whether to respect .gitignore files
+    /// Whether to respect .gitignore/.git/info/exclude/core.excludesFile
     respect_gitignore: bool,
+    /// Whether to respect `.ignore`/`.mccabreignore` files, independent of
+    /// whether the project is a git repo
+    respect_ignore_file: bool,
+    /// Raw include patterns, kept around so the walk can be scoped to their
+    /// literal base directories instead of globbing the whole tree up front
+    include_patterns: Vec<String>,
+    /// Compiled include matchers, reused across the whole walk
+    includes: Option<GlobSet>,
+    /// Compiled exclude matchers, reused across the whole walk
+    excludes: Option<GlobSet>,
+    /// Number of threads to walk directories with (0 = auto, chosen by `ignore`)
+    threads: usize,
+    /// If set, only files whose detected language is in this set are kept
+    allowed_languages: Option<HashSet<Language>>,
+    /// Files whose detected language is in this set are always dropped,
+    /// regardless of `allowed_languages`
+    denied_languages: HashSet<Language>,
 }
 
 impl Default for FileLoader {
     fn default() -> Self {
-        Self { respect_gitignore: true }
+        Self {
+            respect_gitignore: true,
+            respect_ignore_file: true,
+            include_patterns: Vec::new(),
+            includes: None,
+            excludes: None,
+            threads: 0,
+            allowed_languages: None,
+            denied_languages: HashSet::new(),
+        }
     }
 }
 
@@ -35,12 +80,103 @@ impl FileLoader {
         self
     }
 
+    /// Enable or disable `.ignore`/`.mccabreignore` awareness, independent of
+    /// gitignore
+    pub fn with_ignore_file(mut self, respect: bool) -> Self {
+        self.respect_ignore_file = respect;
+        self
+    }
+
+    /// Set how many threads to walk directories with (0 = auto, chosen by `ignore`
+    /// based on available parallelism)
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    /// Restrict analysis to only these languages (ripgrep-style `--type`).
+    /// An empty iterator clears the restriction rather than allowing nothing.
+    pub fn with_languages<I: IntoIterator<Item = Language>>(mut self, languages: I) -> Self {
+        let allowed: HashSet<Language> = languages.into_iter().collect();
+        self.allowed_languages = if allowed.is_empty() { None } else { Some(allowed) };
+        self
+    }
+
+    /// Exclude these languages from analysis (ripgrep-style `--type-not`),
+    /// taking precedence over `with_languages` for any language in both sets.
+    pub fn without_languages<I: IntoIterator<Item = Language>>(mut self, languages: I) -> Self {
+        self.denied_languages = languages.into_iter().collect();
+        self
+    }
+
+    /// Scope the walk to paths matching any of the given include globs (e.g.
+    /// `src/**/*.rs`). Each pattern is split into a concrete base-directory
+    /// prefix plus the remaining wildcard suffix, so the walker only ever
+    /// descends into matching subtrees instead of expanding the glob up front.
+    pub fn with_includes<I, S>(mut self, patterns: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut builder = GlobSetBuilder::new();
+        let mut compiled = Vec::new();
+
+        for pattern in patterns {
+            let pattern = pattern.as_ref();
+            let glob = Glob::new(pattern)
+                .map_err(|e| MccabreError::Glob { pattern: pattern.to_string(), source: e })?;
+            builder.add(glob);
+            compiled.push(pattern.to_string());
+        }
+
+        if !compiled.is_empty() {
+            self.includes = Some(
+                builder
+                    .build()
+                    .map_err(|e| MccabreError::Glob { pattern: compiled.join(", "), source: e })?,
+            );
+            self.include_patterns = compiled;
+        }
+
+        Ok(self)
+    }
+
+    /// Exclude paths matching any of the given globs, matched against each
+    /// walked entry as it is visited so excluded directories are pruned
+    /// early instead of being descended into.
+    pub fn with_excludes<I, S>(mut self, patterns: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut builder = GlobSetBuilder::new();
+        let mut compiled = Vec::new();
+
+        for pattern in patterns {
+            let pattern = pattern.as_ref();
+            let glob = Glob::new(pattern)
+                .map_err(|e| MccabreError::Glob { pattern: pattern.to_string(), source: e })?;
+            builder.add(glob);
+            compiled.push(pattern.to_string());
+        }
+
+        if !compiled.is_empty() {
+            self.excludes = Some(
+                builder
+                    .build()
+                    .map_err(|e| MccabreError::Glob { pattern: compiled.join(", "), source: e })?,
+            );
+        }
+
+        Ok(self)
+    }
+
     /// Load files from a path (file, directory, or list)
     pub fn load<P: AsRef<Path>>(&self, path: P) -> Result<Vec<SourceFile>> {
         let path = path.as_ref();
 
         if path.is_file() {
-            let file = self.load_file(path)?;
+            let file = load_file(path)?;
             Ok(vec![file])
         } else if path.is_dir() {
             self.load_directory(path)
@@ -67,42 +203,248 @@ impl FileLoader {
         Ok(files)
     }
 
-    /// Load a single file
-    fn load_file(&self, path: &Path) -> Result<SourceFile> {
-        let language = Language::from_path(path)?;
-        let content =
-            fs::read_to_string(path).map_err(|e| MccabreError::FileRead { path: path.to_path_buf(), source: e })?;
-
-        Ok(SourceFile { path: path.to_path_buf(), content, language })
-    }
+    /// Load only files changed under `scope`, relative to the git repository
+    /// containing `path`, still applying this loader's include/exclude
+    /// filters and language detection. Deleted paths are skipped, since
+    /// there is nothing left on disk to load. Gitignored files never appear
+    /// in git's change list in the first place, so `respect_gitignore` has
+    /// no effect here.
+    pub fn load_changed<P: AsRef<Path>>(&self, path: P, scope: ChangeScope) -> Result<Vec<SourceFile>> {
+        let root = git_toplevel(path.as_ref())?;
+        let changed = git_changed_paths(&root, &scope)?;
 
-    /// Load all supported files from a directory
-    fn load_directory(&self, dir: &Path) -> Result<Vec<SourceFile>> {
         let mut files = Vec::new();
 
-        let walker = WalkBuilder::new(dir)
-            .standard_filters(self.respect_gitignore)
-            .hidden(false)
-            .parents(true)
-            .build();
-
-        for entry in walker {
-            let entry = entry.map_err(|e| MccabreError::Io(io::Error::other(e.to_string())))?;
-            let path = entry.path();
-
+        for rel in changed {
+            let path = root.join(&rel);
             if !path.is_file() {
                 continue;
             }
 
-            match self.load_file(path) {
+            if let Some(excludes) = &self.excludes {
+                if excludes.is_match(&rel) {
+                    continue;
+                }
+            }
+
+            if let Some(includes) = &self.includes {
+                if !includes.is_match(&rel) {
+                    continue;
+                }
+            }
+
+            match Language::from_path(&path) {
+                Ok(language) if !language_allowed(language, &self.allowed_languages, &self.denied_languages) => {
+                    continue;
+                }
+                _ => {}
+            }
+
+            match load_file(&path) {
                 Ok(file) => files.push(file),
-                Err(MccabreError::UnsupportedFileType(_)) => continue,
+                Err(MccabreError::UnsupportedFileType(_)) => {}
                 Err(e) => return Err(e),
             }
         }
 
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+
         Ok(files)
     }
+
+    /// Load all supported files from a directory, walking and reading files
+    /// in parallel and sorting the results afterwards for stable ordering
+    fn load_directory(&self, dir: &Path) -> Result<Vec<SourceFile>> {
+        let mut files = Vec::new();
+        let seen: Arc<Mutex<HashSet<PathBuf>>> = Arc::new(Mutex::new(HashSet::new()));
+
+        for base in self.walk_base_dirs(dir) {
+            if !base.is_dir() {
+                continue;
+            }
+
+            let mut builder = WalkBuilder::new(&base);
+            builder
+                .hidden(false)
+                .parents(true)
+                .git_ignore(self.respect_gitignore)
+                .git_global(self.respect_gitignore)
+                .git_exclude(self.respect_gitignore)
+                .ignore(self.respect_ignore_file)
+                .threads(self.threads);
+
+            if self.respect_ignore_file {
+                builder.add_custom_ignore_filename(CUSTOM_IGNORE_FILENAME);
+            }
+
+            let dir = dir.to_path_buf();
+            let excludes = self.excludes.clone();
+            let includes = self.includes.clone();
+            let allowed_languages = self.allowed_languages.clone();
+            let denied_languages = self.denied_languages.clone();
+            let (tx, rx) = mpsc::channel::<Result<SourceFile>>();
+
+            builder.build_parallel().run(|| {
+                let tx = tx.clone();
+                let dir = dir.clone();
+                let excludes = excludes.clone();
+                let includes = includes.clone();
+                let allowed_languages = allowed_languages.clone();
+                let denied_languages = denied_languages.clone();
+                let seen = Arc::clone(&seen);
+
+                Box::new(move |entry| {
+                    let entry = match entry {
+                        Ok(entry) => entry,
+                        Err(e) => {
+                            let _ = tx.send(Err(MccabreError::Io(io::Error::other(e.to_string()))));
+                            return WalkState::Quit;
+                        }
+                    };
+
+                    let path = entry.path();
+                    let rel = path.strip_prefix(&dir).unwrap_or(path);
+
+                    if let Some(excludes) = &excludes {
+                        if excludes.is_match(rel) {
+                            return if path.is_dir() { WalkState::Skip } else { WalkState::Continue };
+                        }
+                    }
+
+                    if !path.is_file() {
+                        return WalkState::Continue;
+                    }
+
+                    if let Some(includes) = &includes {
+                        if !includes.is_match(rel) {
+                            return WalkState::Continue;
+                        }
+                    }
+
+                    if let Ok(language) = Language::from_path(path) {
+                        if !language_allowed(language, &allowed_languages, &denied_languages) {
+                            return WalkState::Continue;
+                        }
+                    }
+
+                    if !seen.lock().unwrap().insert(path.to_path_buf()) {
+                        return WalkState::Continue;
+                    }
+
+                    match load_file(path) {
+                        Ok(file) => {
+                            let _ = tx.send(Ok(file));
+                        }
+                        Err(MccabreError::UnsupportedFileType(_)) => {}
+                        Err(e) => {
+                            let _ = tx.send(Err(e));
+                            return WalkState::Quit;
+                        }
+                    }
+
+                    WalkState::Continue
+                })
+            });
+
+            drop(tx);
+
+            for result in rx {
+                files.push(result?);
+            }
+        }
+
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+
+        Ok(files)
+    }
+
+    /// The concrete directories the walk should start from: the literal,
+    /// non-wildcard prefix of each include pattern joined onto `dir`, or
+    /// just `dir` itself when there are no include patterns.
+    fn walk_base_dirs(&self, dir: &Path) -> Vec<PathBuf> {
+        if self.include_patterns.is_empty() {
+            return vec![dir.to_path_buf()];
+        }
+
+        let mut bases: Vec<PathBuf> =
+            self.include_patterns.iter().map(|pattern| dir.join(glob_base_dir(pattern))).collect();
+        bases.sort();
+        bases.dedup();
+
+        bases
+    }
+}
+
+/// Read a single source file from disk and detect its language
+fn load_file(path: &Path) -> Result<SourceFile> {
+    let content =
+        fs::read_to_string(path).map_err(|e| MccabreError::FileRead { path: path.to_path_buf(), source: e })?;
+    let language = Language::detect(path, &content)?;
+
+    Ok(SourceFile { path: path.to_path_buf(), content, language })
+}
+
+/// Whether `language` passes an allow-set (kept if present in it, or if the
+/// allow-set is absent entirely) and a deny-set (dropped if present in it,
+/// which takes precedence over the allow-set).
+fn language_allowed(language: Language, allowed: &Option<HashSet<Language>>, denied: &HashSet<Language>) -> bool {
+    if denied.contains(&language) {
+        return false;
+    }
+
+    match allowed {
+        Some(allowed) => allowed.contains(&language),
+        None => true,
+    }
+}
+
+/// Resolve the root of the git repository containing `path` via
+/// `git rev-parse --show-toplevel`.
+fn git_toplevel(path: &Path) -> Result<PathBuf> {
+    let output = run_git(path, &["rev-parse", "--show-toplevel"])?;
+    Ok(PathBuf::from(output.trim()))
+}
+
+/// List paths added/copied/modified/renamed under `scope`, relative to the
+/// repository root, via `git diff --name-only`.
+fn git_changed_paths(root: &Path, scope: &ChangeScope) -> Result<Vec<PathBuf>> {
+    let mut args = vec!["diff", "--name-only", "--diff-filter=ACMR"];
+    match scope {
+        ChangeScope::Since(rev) => args.push(rev),
+        ChangeScope::Staged => args.push("--cached"),
+    }
+
+    let output = run_git(root, &args)?;
+    Ok(output.lines().filter(|line| !line.is_empty()).map(PathBuf::from).collect())
+}
+
+/// Run a git subcommand rooted at `path` and return its trimmed stdout,
+/// mapping a non-zero exit or a missing `git` binary to [`MccabreError::Git`].
+fn run_git(path: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(path)
+        .args(args)
+        .output()
+        .map_err(|e| MccabreError::Git(format!("failed to run git: {e}")))?;
+
+    if !output.status.success() {
+        return Err(MccabreError::Git(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Split a glob pattern into its concrete, non-wildcard base directory, e.g.
+/// `src/**/*.rs` -> `src`, `*.rs` -> `.`, `tests/fixtures/a.rs` -> `tests/fixtures`.
+fn glob_base_dir(pattern: &str) -> PathBuf {
+    let wildcard_pos = pattern.find(['*', '?', '[', '{']).unwrap_or(pattern.len());
+    let literal_prefix = &pattern[..wildcard_pos];
+
+    match literal_prefix.rfind('/') {
+        Some(idx) => PathBuf::from(&literal_prefix[..idx]),
+        None => PathBuf::from("."),
+    }
 }
 
 #[cfg(test)]
@@ -172,6 +514,164 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_mccabreignore_respected_regardless_of_git() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("included.rs"), "fn included() {}").unwrap();
+
+        let ignored_dir = temp_dir.path().join("vendor");
+        fs::create_dir(&ignored_dir).unwrap();
+        fs::write(ignored_dir.join("excluded.rs"), "fn excluded() {}").unwrap();
+
+        fs::write(temp_dir.path().join(".mccabreignore"), "vendor/\n").unwrap();
+
+        let loader = FileLoader::new();
+        let files = loader.load(temp_dir.path())?;
+
+        assert!(files.iter().any(|f| f.path.ends_with("included.rs")));
+        assert!(!files.iter().any(|f| f.path.ends_with("excluded.rs")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_ignore_disables_both_gitignore_and_custom_ignore_file() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("included.rs"), "fn included() {}").unwrap();
+
+        let git_ignored_dir = temp_dir.path().join("build");
+        fs::create_dir(&git_ignored_dir).unwrap();
+        fs::write(git_ignored_dir.join("excluded.rs"), "fn excluded() {}").unwrap();
+        fs::write(temp_dir.path().join(".gitignore"), "build/\n").unwrap();
+
+        let custom_ignored_dir = temp_dir.path().join("vendor");
+        fs::create_dir(&custom_ignored_dir).unwrap();
+        fs::write(custom_ignored_dir.join("excluded.rs"), "fn excluded() {}").unwrap();
+        fs::write(temp_dir.path().join(".mccabreignore"), "vendor/\n").unwrap();
+
+        let loader = FileLoader::new().with_gitignore(false).with_ignore_file(false);
+        let files = loader.load(temp_dir.path())?;
+
+        assert!(files.iter().any(|f| f.path.ends_with("included.rs")));
+        assert_eq!(files.iter().filter(|f| f.path.ends_with("excluded.rs")).count(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_gitignore_still_honors_custom_ignore_file() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("included.rs"), "fn included() {}").unwrap();
+
+        let custom_ignored_dir = temp_dir.path().join("vendor");
+        fs::create_dir(&custom_ignored_dir).unwrap();
+        fs::write(custom_ignored_dir.join("excluded.rs"), "fn excluded() {}").unwrap();
+        fs::write(temp_dir.path().join(".mccabreignore"), "vendor/\n").unwrap();
+
+        let loader = FileLoader::new().with_gitignore(false);
+        let files = loader.load(temp_dir.path())?;
+
+        assert!(files.iter().any(|f| f.path.ends_with("included.rs")));
+        assert!(!files.iter().any(|f| f.path.ends_with("excluded.rs")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_include_glob_scopes_walk_to_matching_files() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir(&src_dir).unwrap();
+        fs::write(src_dir.join("lib.rs"), "fn included() {}").unwrap();
+
+        let docs_dir = temp_dir.path().join("docs");
+        fs::create_dir(&docs_dir).unwrap();
+        fs::write(docs_dir.join("notes.rs"), "fn excluded() {}").unwrap();
+
+        let loader = FileLoader::new().with_includes(["src/**/*.rs"])?;
+        let files = loader.load(temp_dir.path())?;
+
+        assert!(files.iter().any(|f| f.path.ends_with("src/lib.rs")));
+        assert!(!files.iter().any(|f| f.path.ends_with("notes.rs")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_exclude_glob_prunes_matching_directory() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("included.rs"), "fn included() {}").unwrap();
+
+        let tests_dir = temp_dir.path().join("tests");
+        fs::create_dir(&tests_dir).unwrap();
+        fs::write(tests_dir.join("excluded.rs"), "fn excluded() {}").unwrap();
+
+        let loader = FileLoader::new().with_excludes(["**/tests/**"])?;
+        let files = loader.load(temp_dir.path())?;
+
+        assert!(files.iter().any(|f| f.path.ends_with("included.rs")));
+        assert!(!files.iter().any(|f| f.path.ends_with("excluded.rs")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_languages_keeps_only_allowed_language() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("file1.rs"), "fn test1() {}").unwrap();
+        fs::write(temp_dir.path().join("file2.js"), "function test2() {}").unwrap();
+
+        let loader = FileLoader::new().with_languages([Language::Rust]);
+        let files = loader.load(temp_dir.path())?;
+
+        assert_eq!(files.len(), 1);
+        assert!(files[0].path.ends_with("file1.rs"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_without_languages_drops_denied_language() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("file1.rs"), "fn test1() {}").unwrap();
+        fs::write(temp_dir.path().join("file2.js"), "function test2() {}").unwrap();
+
+        let loader = FileLoader::new().without_languages([Language::JavaScript]);
+        let files = loader.load(temp_dir.path())?;
+
+        assert_eq!(files.len(), 1);
+        assert!(files[0].path.ends_with("file1.rs"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_threads_still_loads_all_files() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("file1.rs"), "fn test1() {}").unwrap();
+        fs::write(temp_dir.path().join("file2.js"), "function test2() {}").unwrap();
+
+        let loader = FileLoader::new().with_threads(4);
+        let files = loader.load(temp_dir.path())?;
+
+        assert_eq!(files.len(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_glob_pattern_is_rejected() {
+        let result = FileLoader::new().with_includes(["["]);
+        assert!(matches!(result, Err(MccabreError::Glob { .. })));
+    }
+
+    #[test]
+    fn test_glob_base_dir_splits_literal_prefix_from_wildcard() {
+        assert_eq!(glob_base_dir("src/**/*.rs"), PathBuf::from("src"));
+        assert_eq!(glob_base_dir("*.rs"), PathBuf::from("."));
+        assert_eq!(glob_base_dir("tests/fixtures/a.rs"), PathBuf::from("tests/fixtures"));
+    }
+
     #[test]
     fn test_unsupported_file_type() {
         let temp_dir = TempDir::new().unwrap();
@@ -200,4 +700,64 @@ mod tests {
 
         Ok(())
     }
+
+    /// Run a git command in `dir`, panicking with its stderr on failure.
+    /// Kept separate from `run_git` above since tests want to assert on
+    /// setup succeeding unconditionally rather than propagate a `Result`.
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.email=test@example.com", "-c", "user.name=test"])
+            .args(args)
+            .output()
+            .expect("git must be installed to run this test");
+
+        assert!(status.status.success(), "git {args:?} failed: {}", String::from_utf8_lossy(&status.stderr));
+    }
+
+    #[test]
+    fn test_load_changed_since_ref_excludes_unmodified_files() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+
+        git(dir, &["init"]);
+        fs::write(dir.join("unchanged.rs"), "fn unchanged() {}").unwrap();
+        fs::write(dir.join("changed.rs"), "fn before() {}").unwrap();
+        git(dir, &["add", "."]);
+        git(dir, &["commit", "-m", "initial"]);
+
+        fs::write(dir.join("changed.rs"), "fn after() {}").unwrap();
+
+        let loader = FileLoader::new();
+        let files = loader.load_changed(dir, ChangeScope::Since("HEAD".to_string()))?;
+
+        assert_eq!(files.len(), 1);
+        assert!(files[0].path.ends_with("changed.rs"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_changed_staged_only_includes_index() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+
+        git(dir, &["init"]);
+        fs::write(dir.join("tracked.rs"), "fn tracked() {}").unwrap();
+        git(dir, &["add", "."]);
+        git(dir, &["commit", "-m", "initial"]);
+
+        fs::write(dir.join("staged.rs"), "fn staged() {}").unwrap();
+        fs::write(dir.join("unstaged.rs"), "fn unstaged() {}").unwrap();
+        git(dir, &["add", "staged.rs"]);
+
+        let loader = FileLoader::new();
+        let files = loader.load_changed(dir, ChangeScope::Staged)?;
+
+        assert_eq!(files.len(), 1);
+        assert!(files[0].path.ends_with("staged.rs"));
+
+        Ok(())
+    }
 }
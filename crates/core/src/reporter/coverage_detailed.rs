@@ -1,12 +1,14 @@
-use crate::coverage::FileCoverage;
+use crate::complexity::loc::{self, LineKind};
+use crate::coverage::{BranchHit, FileCoverage};
 use crate::highlight::Highlighter;
+use crate::tokenizer::Language;
 
 use super::coverage_term::strip_ansi_codes;
 
 use owo_colors::OwoColorize;
 
 pub fn report_detailed_file_view(
-    file: &FileCoverage, source_code: &str, file_extension: &str, truncate_threshold: usize,
+    file: &FileCoverage, source_code: &str, language: Language, file_extension: &str, truncate_threshold: usize,
 ) -> String {
     let mut output = String::new();
 
@@ -21,14 +23,24 @@ pub fn report_detailed_file_view(
     let max_line_num = lines.len();
     let line_num_width = max_line_num.to_string().len();
 
+    let line_kinds = loc::classify_lines(source_code, language).unwrap_or_else(|_| vec![LineKind::Code; lines.len()]);
+
     let mut current_range_start: Option<usize> = None;
     let mut range_lines: Vec<(usize, &str)> = Vec::new();
 
     for (line_idx, line) in lines.iter().enumerate() {
         let line_num = line_idx + 1;
         let hit_count = file.lines.get(&(line_num as u32));
+        let kind = line_kinds.get(line_idx).copied().unwrap_or(LineKind::Code);
 
-        if is_ignored_line(hit_count) {
+        if matches!(kind, LineKind::Blank | LineKind::Comment) {
+            if let Some(_start) = current_range_start.take() {
+                output.push_str(&handle_range(&range_lines, line_num_width, truncate_threshold));
+                range_lines.clear();
+            }
+            output.push_str(&render_neutral_line(line, line_num, line_num_width));
+            output.push('\n');
+        } else if is_ignored_line(hit_count) {
             if current_range_start.is_none() {
                 current_range_start = Some(line_num);
             }
@@ -38,7 +50,8 @@ pub fn report_detailed_file_view(
                 output.push_str(&handle_range(&range_lines, line_num_width, truncate_threshold));
                 range_lines.clear();
             }
-            let line_output = render_line_with_coverage(line, line_num, hit_count, line_num_width);
+            let branches = file.branches_for_line(line_num as u32);
+            let line_output = render_line_with_coverage(line, line_num, hit_count, &branches, line_num_width);
             output.push_str(&line_output);
             output.push('\n');
         }
@@ -91,7 +104,12 @@ fn render_header(file: &FileCoverage) -> String {
     output
 }
 
-fn render_line_with_coverage(line: &str, line_num: usize, hit_count: Option<&u64>, line_num_width: usize) -> String {
+fn render_line_with_coverage(
+    line: &str, line_num: usize, hit_count: Option<&u64>, branches: &[BranchHit], line_num_width: usize,
+) -> String {
+    let partial_branches =
+        hit_count.is_some_and(|&count| count > 0) && branches.iter().any(|b| b.taken == Some(0));
+
     let (hit_str, marker, styled_line) = match hit_count {
         Some(0) => {
             let clean_line = strip_ansi_codes(line);
@@ -101,6 +119,14 @@ fn render_line_with_coverage(line: &str, line_num: usize, hit_count: Option<&u64
                 clean_line.bright_red().bold().to_string(),
             )
         }
+        Some(_count) if partial_branches => {
+            let taken = branches.iter().filter(|b| matches!(b.taken, Some(n) if n > 0)).count();
+            (
+                format!(" {taken}/{}", branches.len()).yellow().to_string(),
+                " ~".yellow().bold().to_string(),
+                line.dimmed().to_string(),
+            )
+        }
         Some(_count) => (
             " ✓".green().to_string(),
             " |".green().to_string(),
@@ -127,6 +153,20 @@ fn is_ignored_line(hit_count: Option<&u64>) -> bool {
     hit_count.is_none()
 }
 
+/// Renders a blank or comment-only line with a marker distinct from both
+/// covered/missed code and genuinely un-instrumented executable code, so it
+/// neither inflates nor deflates the hit/miss accounting.
+fn render_neutral_line(line: &str, line_num: usize, line_num_width: usize) -> String {
+    format!(
+        "{:>width$}  {} {} {}",
+        line_num,
+        "  ",
+        " ·".dimmed().to_string(),
+        line.dimmed().to_string(),
+        width = line_num_width
+    )
+}
+
 fn render_truncation_marker(line_num_width: usize) -> String {
     format!("{:>width$}  {} {}", "", " -", " . ...".dimmed(), width = line_num_width)
 }
@@ -138,15 +178,15 @@ fn handle_range(lines: &[(usize, &str)], line_num_width: usize, threshold: usize
         let (first_num, first_line) = lines.first().unwrap();
         let (last_num, last_line) = lines.last().unwrap();
 
-        output.push_str(&render_line_with_coverage(first_line, *first_num, None, line_num_width));
+        output.push_str(&render_line_with_coverage(first_line, *first_num, None, &[], line_num_width));
         output.push('\n');
         output.push_str(&render_truncation_marker(line_num_width));
         output.push('\n');
-        output.push_str(&render_line_with_coverage(last_line, *last_num, None, line_num_width));
+        output.push_str(&render_line_with_coverage(last_line, *last_num, None, &[], line_num_width));
         output.push('\n');
     } else {
         for (line_num, line) in lines {
-            output.push_str(&render_line_with_coverage(line, *line_num, None, line_num_width));
+            output.push_str(&render_line_with_coverage(line, *line_num, None, &[], line_num_width));
             output.push('\n');
         }
     }
@@ -223,7 +263,7 @@ mod tests {
     #[test]
     fn test_render_line_with_coverage_hit() {
         let line = "fn main() {}";
-        let output = render_line_with_coverage(line, 1, Some(&10), 3);
+        let output = render_line_with_coverage(line, 1, Some(&10), &[], 3);
         let output = strip_ansi_codes(&output);
 
         assert!(output.contains("1"));
@@ -235,7 +275,7 @@ mod tests {
     #[test]
     fn test_render_line_with_coverage_miss() {
         let line = "println!(\"hello\");";
-        let output = render_line_with_coverage(line, 2, Some(&0), 3);
+        let output = render_line_with_coverage(line, 2, Some(&0), &[], 3);
         let output = strip_ansi_codes(&output);
 
         assert!(output.contains("2"));
@@ -247,7 +287,7 @@ mod tests {
     #[test]
     fn test_render_line_with_coverage_none() {
         let line = "// comment";
-        let output = render_line_with_coverage(line, 1, None, 3);
+        let output = render_line_with_coverage(line, 1, None, &[], 3);
         let output = strip_ansi_codes(&output);
 
         assert!(output.contains("1"));
@@ -256,6 +296,35 @@ mod tests {
         assert!(output.contains("// comment"));
     }
 
+    #[test]
+    fn test_render_line_with_coverage_partial_branches() {
+        let line = "if a && b {}";
+        let branches = [
+            BranchHit { block: 0, branch: 0, taken: Some(3) },
+            BranchHit { block: 0, branch: 1, taken: Some(0) },
+        ];
+        let output = render_line_with_coverage(line, 1, Some(&3), &branches, 3);
+        let output = strip_ansi_codes(&output);
+
+        assert!(output.contains("~"));
+        assert!(output.contains("1/2"));
+        assert!(output.contains("if a && b {}"));
+    }
+
+    #[test]
+    fn test_render_line_with_coverage_ignores_fully_taken_branches() {
+        let line = "if a {}";
+        let branches = [
+            BranchHit { block: 0, branch: 0, taken: Some(3) },
+            BranchHit { block: 0, branch: 1, taken: Some(2) },
+        ];
+        let output = render_line_with_coverage(line, 1, Some(&3), &branches, 3);
+        let output = strip_ansi_codes(&output);
+
+        assert!(!output.contains('~'));
+        assert!(output.contains('✓'));
+    }
+
     #[test]
     fn test_report_directory_view() {
         let file1 = create_test_file_coverage("src/lib.rs");
@@ -285,7 +354,7 @@ mod tests {
         let file = create_test_file_coverage("test.rs");
         let source_code = "fn main() {\n    println!(\"Hello\");\n    return;\n}";
 
-        let output = report_detailed_file_view(&file, source_code, "rs", 5);
+        let output = report_detailed_file_view(&file, source_code, Language::Rust, "rs", 5);
         let output = strip_ansi_codes(&output);
 
         assert!(output.contains("FILE: test.rs"));
@@ -293,12 +362,28 @@ mod tests {
         assert!(output.contains("Hello"));
     }
 
+    #[test]
+    fn test_blank_and_comment_lines_render_with_neutral_marker() {
+        let mut lines = BTreeMap::new();
+        lines.insert(1, 10);
+        lines.insert(4, 5);
+
+        let file = FileCoverage::new("test.rs".to_string(), lines);
+        let source_code = "fn main() {\n\n    // a comment\n    return;\n}";
+
+        let output = report_detailed_file_view(&file, source_code, Language::Rust, "rs", 5);
+        let output = strip_ansi_codes(&output);
+
+        assert!(output.contains('·'));
+        assert!(!output.contains("..."));
+    }
+
     #[test]
     fn test_truncation_default_threshold() {
         let file = create_test_file_coverage("test.rs");
         let source_code = (1..100).map(|i| format!("line {}", i)).collect::<Vec<_>>().join("\n");
 
-        let output = report_detailed_file_view(&file, &source_code, "rs", 5);
+        let output = report_detailed_file_view(&file, &source_code, Language::Rust, "rs", 5);
         let output = strip_ansi_codes(&output);
 
         assert!(output.contains("..."));
@@ -314,7 +399,7 @@ mod tests {
         let file = FileCoverage::new("test.rs".to_string(), lines);
         let source_code = (1..10).map(|i| format!("line {}", i)).collect::<Vec<_>>().join("\n");
 
-        let output = report_detailed_file_view(&file, &source_code, "rs", 5);
+        let output = report_detailed_file_view(&file, &source_code, Language::Rust, "rs", 5);
         let output = strip_ansi_codes(&output);
 
         assert!(!output.contains("..."));
@@ -325,7 +410,7 @@ mod tests {
         let file = create_test_file_coverage("test.rs");
         let source_code = (1..100).map(|i| format!("line {}", i)).collect::<Vec<_>>().join("\n");
 
-        let output = report_detailed_file_view(&file, &source_code, "rs", 10);
+        let output = report_detailed_file_view(&file, &source_code, Language::Rust, "rs", 10);
         let output = strip_ansi_codes(&output);
 
         assert!(output.contains("..."));
@@ -339,7 +424,7 @@ mod tests {
         let file = FileCoverage::new("test.rs".to_string(), lines);
         let source_code = (1..100).map(|i| format!("line {}", i)).collect::<Vec<_>>().join("\n");
 
-        let output = report_detailed_file_view(&file, &source_code, "rs", 5);
+        let output = report_detailed_file_view(&file, &source_code, Language::Rust, "rs", 5);
         let output = strip_ansi_codes(&output);
 
         assert!(output.contains("line 1"));
@@ -357,7 +442,7 @@ mod tests {
         let file = FileCoverage::new("test.rs".to_string(), lines);
         let source_code = (1..=100).map(|i| format!("line {}", i)).collect::<Vec<_>>().join("\n");
 
-        let output = report_detailed_file_view(&file, &source_code, "rs", 5);
+        let output = report_detailed_file_view(&file, &source_code, Language::Rust, "rs", 5);
         let output = strip_ansi_codes(&output);
 
         assert!(output.contains("✓"));
@@ -0,0 +1,238 @@
+use crate::Result;
+use crate::complexity::cyclomatic::CyclomaticMetrics;
+use crate::coverage::FileCoverage;
+use crate::tokenizer::Language;
+use owo_colors::OwoColorize;
+
+/// A function that is simultaneously high-complexity and low-coverage — the
+/// kind of code a reviewer most wants to see flagged first, rather than
+/// complexity and coverage being reported in isolation.
+#[derive(Debug, Clone)]
+pub struct Hotspot {
+    pub name: String,
+    pub line: usize,
+    pub end_line: usize,
+    pub complexity: usize,
+    pub coverage_rate: f64,
+}
+
+/// Correlate [`CyclomaticMetrics`]'s per-function complexity with `file`'s
+/// per-line hit counts: for each function detected in `source`, compute its
+/// coverage rate from the lines falling inside its brace span, and keep the
+/// ones that clear both `complexity_threshold` and fall under
+/// `coverage_rate_threshold`. Results are sorted worst-first (lowest coverage,
+/// then highest complexity) so the riskiest functions surface immediately.
+pub fn find_hotspots(
+    file: &FileCoverage, source: &str, language: Language, complexity_threshold: usize, coverage_rate_threshold: f64,
+) -> Result<Vec<Hotspot>> {
+    let metrics = CyclomaticMetrics::calculate(source, language)?;
+
+    let mut hotspots: Vec<Hotspot> = metrics
+        .functions
+        .into_iter()
+        .filter_map(|func| {
+            let coverage_rate = function_coverage_rate(file, func.line, func.end_line);
+
+            if func.complexity >= complexity_threshold && coverage_rate < coverage_rate_threshold {
+                Some(Hotspot {
+                    name: func.name,
+                    line: func.line,
+                    end_line: func.end_line,
+                    complexity: func.complexity,
+                    coverage_rate,
+                })
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    hotspots.sort_by(|a, b| {
+        a.coverage_rate
+            .partial_cmp(&b.coverage_rate)
+            .unwrap()
+            .then_with(|| b.complexity.cmp(&a.complexity))
+    });
+
+    Ok(hotspots)
+}
+
+/// The hit rate of the lines in `[start_line, end_line]` that `file` has hit
+/// counts for. A function with no instrumented lines in that range reports
+/// `100.0` rather than `0.0`, so an un-instrumentable signature-only span
+/// can't masquerade as a coverage gap.
+fn function_coverage_rate(file: &FileCoverage, start_line: usize, end_line: usize) -> f64 {
+    let mut hit = 0usize;
+    let mut total = 0usize;
+
+    for (_, &count) in file.lines.range(start_line as u32..=end_line as u32) {
+        total += 1;
+        if count > 0 {
+            hit += 1;
+        }
+    }
+
+    if total == 0 { 100.0 } else { (hit as f64 / total as f64) * 100.0 }
+}
+
+/// Render a worst-first table of hotspots, reusing the rate coloring scheme
+/// from [`super::coverage_detailed::render_header`].
+pub fn report_hotspots(file: &FileCoverage, hotspots: &[Hotspot]) -> String {
+    let mut output = String::new();
+
+    output.push_str(&"═".repeat(80).bright_cyan().to_string());
+    output.push('\n');
+    output.push_str(&format!("HOTSPOTS: {}", file.path.bold()));
+    output.push('\n');
+    output.push_str(&"═".repeat(80).bright_cyan().to_string());
+    output.push('\n');
+
+    if hotspots.is_empty() {
+        output.push_str(&"No high-complexity, low-coverage functions found.".green().to_string());
+        output.push('\n');
+        return output;
+    }
+
+    for hotspot in hotspots {
+        output.push_str(&format_hotspot_row(hotspot));
+    }
+
+    output
+}
+
+fn format_hotspot_row(hotspot: &Hotspot) -> String {
+    let rate_text = if hotspot.coverage_rate >= 80.0 {
+        format!("{:.2}%", hotspot.coverage_rate).green().bold().to_string()
+    } else if hotspot.coverage_rate >= 50.0 {
+        format!("{:.2}%", hotspot.coverage_rate).yellow().bold().to_string()
+    } else {
+        format!("{:.2}%", hotspot.coverage_rate).red().bold().to_string()
+    };
+
+    let complexity_text = match hotspot.complexity {
+        1..=10 => hotspot.complexity.to_string().green().bold().to_string(),
+        11..=20 => hotspot.complexity.to_string().yellow().bold().to_string(),
+        _ => hotspot.complexity.to_string().red().bold().to_string(),
+    };
+
+    format!(
+        "{}:{}-{}  complexity {} | coverage {}\n",
+        hotspot.name, hotspot.line, hotspot.end_line, complexity_text, rate_text
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn strip_ansi_codes(s: &str) -> String {
+        let mut result = String::new();
+        let mut chars = s.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                if chars.peek() == Some(&'[') {
+                    chars.next();
+                    while let Some(&c) = chars.peek() {
+                        chars.next();
+                        if c.is_ascii_alphabetic() {
+                            break;
+                        }
+                    }
+                }
+            } else {
+                result.push(c);
+            }
+        }
+
+        result
+    }
+
+    fn source() -> &'static str {
+        r#"
+fn simple() {
+    let x = 5;
+}
+
+fn complex(x: i32, y: i32) {
+    if x > 0 && y > 0 {
+        while x < 10 {
+            x += 1;
+        }
+    } else if x < 0 {
+        for i in 0..5 {
+            println!("{}", i);
+        }
+    }
+}
+"#
+    }
+
+    #[test]
+    fn test_find_hotspots_flags_complex_uncovered_function() {
+        let mut lines = BTreeMap::new();
+        lines.insert(2, 1);
+        lines.insert(3, 1);
+        for line in 7..16 {
+            lines.insert(line, 0);
+        }
+
+        let file = FileCoverage::new("test.rs".to_string(), lines);
+        let hotspots = find_hotspots(&file, source(), Language::Rust, 5, 50.0).unwrap();
+
+        assert_eq!(hotspots.len(), 1);
+        assert_eq!(hotspots[0].name, "complex");
+        assert_eq!(hotspots[0].coverage_rate, 0.0);
+    }
+
+    #[test]
+    fn test_find_hotspots_excludes_well_covered_functions() {
+        let mut lines = BTreeMap::new();
+        for line in 7..16 {
+            lines.insert(line, 1);
+        }
+
+        let file = FileCoverage::new("test.rs".to_string(), lines);
+        let hotspots = find_hotspots(&file, source(), Language::Rust, 5, 50.0).unwrap();
+
+        assert!(hotspots.is_empty());
+    }
+
+    #[test]
+    fn test_find_hotspots_excludes_low_complexity_functions() {
+        let mut lines = BTreeMap::new();
+        lines.insert(2, 0);
+        lines.insert(3, 0);
+
+        let file = FileCoverage::new("test.rs".to_string(), lines);
+        let hotspots = find_hotspots(&file, source(), Language::Rust, 5, 50.0).unwrap();
+
+        assert!(hotspots.iter().all(|h| h.name != "simple"));
+    }
+
+    #[test]
+    fn test_report_hotspots_renders_worst_first() {
+        let mut lines = BTreeMap::new();
+        for line in 7..16 {
+            lines.insert(line, 0);
+        }
+
+        let file = FileCoverage::new("test.rs".to_string(), lines);
+        let hotspots = find_hotspots(&file, source(), Language::Rust, 5, 50.0).unwrap();
+        let output = report_hotspots(&file, &hotspots);
+        let output = strip_ansi_codes(&output);
+
+        assert!(output.contains("HOTSPOTS: test.rs"));
+        assert!(output.contains("complex"));
+    }
+
+    #[test]
+    fn test_report_hotspots_empty_is_reassuring() {
+        let file = FileCoverage::new("test.rs".to_string(), BTreeMap::new());
+        let output = report_hotspots(&file, &[]);
+        let output = strip_ansi_codes(&output);
+
+        assert!(output.contains("No high-complexity"));
+    }
+}
@@ -0,0 +1,244 @@
+use crate::coverage::{CoverageReport, FileCoverage};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Renders a [`CoverageReport`] as a directory of static HTML pages: one
+/// page per file with each line prefixed by its execution count, plus an
+/// `index.html` listing every file with its rate and a color bar. This is
+/// the static-site analogue of `grcov`/Deno's HTML coverage reporters.
+pub struct HtmlReporter<'a> {
+    report: &'a CoverageReport,
+}
+
+impl<'a> HtmlReporter<'a> {
+    pub fn new(report: &'a CoverageReport) -> Self {
+        Self { report }
+    }
+
+    /// Write the report to `dir`, creating it if needed. Source for each
+    /// file is read from `source_root.join(file.path)` (or just `file.path`
+    /// when `source_root` is `None`); a file that can't be read is rendered
+    /// with no source lines rather than failing the whole report.
+    pub fn write_to_dir(&self, dir: &Path, source_root: Option<&Path>) -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+
+        for file in &self.report.files {
+            let source_path = match source_root {
+                Some(root) => root.join(&file.path),
+                None => Path::new(&file.path).to_path_buf(),
+            };
+            let source = fs::read_to_string(&source_path).unwrap_or_default();
+
+            let page = render_file_page(file, &source);
+            fs::write(dir.join(html_filename(&file.path)), page)?;
+        }
+
+        fs::write(dir.join("index.html"), render_index(self.report))?;
+
+        Ok(())
+    }
+}
+
+/// Flatten a coverage path into a safe, unique filename, e.g.
+/// `"src/foo/bar.rs"` -> `"src_foo_bar.rs.html"`.
+fn html_filename(path: &str) -> String {
+    format!("{}.html", path.replace(['/', '\\'], "_"))
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn rate_class(rate: f64) -> &'static str {
+    if rate >= 80.0 {
+        "rate-high"
+    } else if rate >= 50.0 {
+        "rate-medium"
+    } else {
+        "rate-low"
+    }
+}
+
+const STYLE: &str = r#"
+body { font-family: monospace; background: #1e1e1e; color: #d4d4d4; margin: 0; padding: 1.5rem; }
+h1 { font-size: 1.1rem; }
+table { border-collapse: collapse; width: 100%; }
+td, th { padding: 0.25rem 0.5rem; text-align: left; }
+.bar { display: inline-block; height: 0.6rem; background: #3c3; vertical-align: middle; }
+.rate-high { color: #6a6; }
+.rate-medium { color: #cc6; }
+.rate-low { color: #c66; }
+pre { margin: 0; }
+.line { display: flex; }
+.count { width: 4rem; text-align: right; padding-right: 0.5rem; color: #888; user-select: none; }
+.code { white-space: pre; }
+.covered { background: rgba(60, 204, 60, 0.12); }
+.uncovered { background: rgba(204, 60, 60, 0.18); }
+"#;
+
+fn render_file_page(file: &FileCoverage, source: &str) -> String {
+    let mut lines_html = String::new();
+
+    for (idx, line) in source.lines().enumerate() {
+        let line_num = (idx + 1) as u32;
+        let hit_count = file.lines.get(&line_num);
+
+        let (class, count_text) = match hit_count {
+            Some(0) => ("uncovered", "0".to_string()),
+            Some(n) => ("covered", n.to_string()),
+            None => ("irrelevant", String::new()),
+        };
+
+        lines_html.push_str(&format!(
+            "<div class=\"line {class}\"><span class=\"count\">{count_text}</span><span class=\"code\">{}</span></div>\n",
+            escape_html(line)
+        ));
+    }
+
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>{path}</title>
+<style>{STYLE}</style>
+</head>
+<body>
+<h1>{path}</h1>
+<p class="{rate_class}">Lines: {hit} / {total} ({rate:.2}%)</p>
+<pre>
+{lines_html}</pre>
+</body>
+</html>
+"#,
+        path = escape_html(&file.path),
+        rate_class = rate_class(file.summary.rate),
+        hit = file.summary.hit,
+        total = file.summary.total,
+        rate = file.summary.rate,
+    )
+}
+
+fn render_index(report: &CoverageReport) -> String {
+    let mut rows = String::new();
+
+    for file in &report.files {
+        rows.push_str(&format!(
+            r#"<tr>
+<td><a href="{href}">{path}</a></td>
+<td class="{rate_class}">{rate:.2}%</td>
+<td><span class="bar" style="width: {bar_width}px"></span></td>
+<td>{hit} / {total}</td>
+</tr>
+"#,
+            href = html_filename(&file.path),
+            path = escape_html(&file.path),
+            rate_class = rate_class(file.summary.rate),
+            rate = file.summary.rate,
+            bar_width = (file.summary.rate / 100.0 * 100.0).round() as u32,
+            hit = file.summary.hit,
+            total = file.summary.total,
+        ));
+    }
+
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Coverage Report</title>
+<style>{STYLE}</style>
+</head>
+<body>
+<h1>Coverage Report</h1>
+<p class="{totals_rate_class}">Total: {hit} / {total} ({rate:.2}%)</p>
+<table>
+<thead><tr><th>File</th><th>Rate</th><th></th><th>Lines</th></tr></thead>
+<tbody>
+{rows}</tbody>
+</table>
+</body>
+</html>
+"#,
+        totals_rate_class = rate_class(report.totals.rate),
+        hit = report.totals.hit,
+        total = report.totals.total,
+        rate = report.totals.rate,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use tempfile::tempdir;
+
+    fn file(path: &str) -> FileCoverage {
+        let mut lines = BTreeMap::new();
+        lines.insert(1, 10);
+        lines.insert(2, 0);
+        lines.insert(3, 5);
+        FileCoverage::new(path.to_string(), lines)
+    }
+
+    #[test]
+    fn test_html_filename_flattens_separators() {
+        assert_eq!(html_filename("src/foo/bar.rs"), "src_foo_bar.rs.html");
+    }
+
+    #[test]
+    fn test_escape_html_escapes_special_chars() {
+        assert_eq!(escape_html("a < b && c > d"), "a &lt; b &amp;&amp; c &gt; d");
+    }
+
+    #[test]
+    fn test_render_file_page_marks_covered_and_uncovered_lines() {
+        let file = file("test.rs");
+        let source = "fn main() {\n    uncovered();\n    covered();\n}";
+
+        let page = render_file_page(&file, source);
+
+        assert!(page.contains("test.rs"));
+        assert!(page.contains("class=\"line uncovered\""));
+        assert!(page.contains("class=\"line covered\""));
+    }
+
+    #[test]
+    fn test_render_index_lists_files() {
+        let report = CoverageReport::new(vec![file("a.rs"), file("b.rs")]);
+        let index = render_index(&report);
+
+        assert!(index.contains("a.rs"));
+        assert!(index.contains("b.rs"));
+        assert!(index.contains("href=\"a.rs.html\""));
+    }
+
+    #[test]
+    fn test_write_to_dir_creates_index_and_file_pages() {
+        let dir = tempdir().unwrap();
+        let src_dir = tempdir().unwrap();
+        fs::write(src_dir.path().join("test.rs"), "fn main() {\n    println!(\"hi\");\n}").unwrap();
+
+        let report = CoverageReport::new(vec![file("test.rs")]);
+        let reporter = HtmlReporter::new(&report);
+        reporter.write_to_dir(dir.path(), Some(src_dir.path())).unwrap();
+
+        assert!(dir.path().join("index.html").exists());
+        assert!(dir.path().join("test.rs.html").exists());
+
+        let page = fs::read_to_string(dir.path().join("test.rs.html")).unwrap();
+        assert!(page.contains("println"));
+    }
+
+    #[test]
+    fn test_write_to_dir_handles_unreadable_source() {
+        let dir = tempdir().unwrap();
+
+        let report = CoverageReport::new(vec![file("missing.rs")]);
+        let reporter = HtmlReporter::new(&report);
+        reporter.write_to_dir(dir.path(), None).unwrap();
+
+        assert!(dir.path().join("missing.rs.html").exists());
+    }
+}
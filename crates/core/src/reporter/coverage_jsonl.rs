@@ -120,6 +120,25 @@ mod tests {
         assert!(content.contains("test.rs"));
     }
 
+    #[test]
+    fn test_jsonl_reporter_serialization_with_branches() {
+        let mut lines = BTreeMap::new();
+        lines.insert(1, 10);
+
+        let mut branches = BTreeMap::new();
+        branches.insert((1, 0, 0), Some(3));
+        branches.insert((1, 0, 1), None);
+
+        let file = FileCoverage::with_branches("test.rs".to_string(), lines, branches);
+        let mut reporter = JsonlReporter::new();
+        reporter.add_file(&file);
+
+        let output = reporter.as_string();
+        let parsed: FileCoverage = serde_json::from_str(&output).unwrap();
+        assert_eq!(parsed.branches.get(&(1, 0, 0)), Some(&Some(3)));
+        assert_eq!(parsed.branches.get(&(1, 0, 1)), Some(&None));
+    }
+
     #[test]
     fn test_jsonl_reporter_serialization() {
         let mut lines = BTreeMap::new();
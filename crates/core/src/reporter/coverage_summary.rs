@@ -0,0 +1,169 @@
+use crate::coverage::CoverageReport;
+use owo_colors::OwoColorize;
+use std::collections::BTreeMap;
+
+/// Aggregated line hit/miss counts for a directory, rolled up from every
+/// file beneath it.
+#[derive(Debug, Clone, Default)]
+struct DirStats {
+    hit: usize,
+    miss: usize,
+}
+
+impl DirStats {
+    fn total(&self) -> usize {
+        self.hit + self.miss
+    }
+
+    fn rate(&self) -> f64 {
+        if self.total() > 0 { (self.hit as f64 / self.total() as f64) * 100.0 } else { 0.0 }
+    }
+}
+
+/// Every directory prefix of `path`, from its immediate parent up to the
+/// root, e.g. `"src/foo/bar.rs"` yields `["src/foo", "src"]`.
+fn ancestor_dirs(path: &str) -> Vec<String> {
+    let mut components: Vec<&str> = path.split('/').filter(|c| !c.is_empty()).collect();
+    components.pop();
+
+    let mut dirs = Vec::with_capacity(components.len());
+    while !components.is_empty() {
+        dirs.push(components.join("/"));
+        components.pop();
+    }
+
+    dirs
+}
+
+/// Print a compact directory-rollup table: one row per directory prefix
+/// found across all files, each showing the summed line hit/miss/rate of
+/// everything beneath it, followed by an "All files" total row. Unlike
+/// [`super::coverage_detailed::report_directory_view`], which lists files
+/// within a single directory, this aggregates every directory in the tree
+/// at once so large repos get a quick per-module overview.
+pub fn report_directory_summary(report: &CoverageReport) -> String {
+    let mut dirs: BTreeMap<String, DirStats> = BTreeMap::new();
+
+    for file in &report.files {
+        for dir in ancestor_dirs(&file.path) {
+            let entry = dirs.entry(dir).or_default();
+            entry.hit += file.summary.hit;
+            entry.miss += file.summary.miss;
+        }
+    }
+
+    let mut output = String::new();
+
+    output.push_str(&"=".repeat(80).cyan().to_string());
+    output.push('\n');
+    output.push_str(&"COVERAGE BY DIRECTORY".cyan().bold().to_string());
+    output.push('\n');
+    output.push_str(&"=".repeat(80).cyan().to_string());
+    output.push_str("\n\n");
+
+    for (path, stats) in &dirs {
+        let depth = path.matches('/').count();
+        let label = path.rsplit('/').next().unwrap_or(path);
+        output.push_str(&format_row(&format!("{}{}", "  ".repeat(depth), label), stats));
+    }
+
+    output.push_str(&"-".repeat(80).cyan().to_string());
+    output.push('\n');
+    output.push_str(&format_row("All files", &DirStats { hit: report.totals.hit, miss: report.totals.miss }));
+    output.push_str(&"=".repeat(80).cyan().to_string());
+    output.push('\n');
+
+    output
+}
+
+fn format_row(label: &str, stats: &DirStats) -> String {
+    let rate = stats.rate();
+    let rate_text = if rate >= 80.0 {
+        format!("{:.2}%", rate).green().bold().to_string()
+    } else if rate >= 50.0 {
+        format!("{:.2}%", rate).yellow().bold().to_string()
+    } else {
+        format!("{:.2}%", rate).red().bold().to_string()
+    };
+
+    format!("{:<40} {} / {} ({})\n", label, stats.hit, stats.total(), rate_text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coverage::FileCoverage;
+    use std::collections::BTreeMap as LineMap;
+
+    fn strip_ansi_codes(s: &str) -> String {
+        let mut result = String::new();
+        let mut chars = s.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                if chars.peek() == Some(&'[') {
+                    chars.next();
+                    while let Some(&c) = chars.peek() {
+                        chars.next();
+                        if c.is_ascii_alphabetic() {
+                            break;
+                        }
+                    }
+                }
+            } else {
+                result.push(c);
+            }
+        }
+
+        result
+    }
+
+    fn file(path: &str, hit_lines: &[u32], miss_lines: &[u32]) -> FileCoverage {
+        let mut lines = LineMap::new();
+        for &l in hit_lines {
+            lines.insert(l, 1);
+        }
+        for &l in miss_lines {
+            lines.insert(l, 0);
+        }
+        FileCoverage::new(path.to_string(), lines)
+    }
+
+    #[test]
+    fn test_ancestor_dirs_nested_file() {
+        assert_eq!(ancestor_dirs("src/foo/bar.rs"), vec!["src/foo", "src"]);
+    }
+
+    #[test]
+    fn test_ancestor_dirs_top_level_file() {
+        assert!(ancestor_dirs("main.rs").is_empty());
+    }
+
+    #[test]
+    fn test_report_directory_summary_rolls_up_to_ancestors() {
+        let report = CoverageReport::new(vec![
+            file("src/foo/a.rs", &[1, 2], &[]),
+            file("src/foo/b.rs", &[1], &[2]),
+            file("src/bar/c.rs", &[1], &[]),
+        ]);
+
+        let output = report_directory_summary(&report);
+        let output = strip_ansi_codes(&output);
+
+        assert!(output.contains("src"));
+        assert!(output.contains("foo"));
+        assert!(output.contains("bar"));
+        assert!(output.contains("All files"));
+    }
+
+    #[test]
+    fn test_report_directory_summary_totals_match_report() {
+        let report = CoverageReport::new(vec![file("src/a.rs", &[1], &[2])]);
+
+        let output = report_directory_summary(&report);
+        let output = strip_ansi_codes(&output);
+
+        assert!(output.contains("All files"));
+        assert!(output.contains("1 / 2"));
+    }
+}
@@ -106,7 +106,36 @@ pub fn report_coverage(report: &CoverageReport) -> String {
     } else {
         format!("{:.2}%", report.totals.rate).red().bold().to_string()
     };
-    output.push_str(&format!("Coverage rate:              {}\n\n", rate_text));
+    output.push_str(&format!("Coverage rate:              {}\n", rate_text));
+
+    if let Some(branch_rate) = report.totals.branch_rate {
+        let branch_rate_text = if branch_rate >= 80.0 {
+            format!("{:.2}%", branch_rate).green().bold().to_string()
+        } else if branch_rate >= 50.0 {
+            format!("{:.2}%", branch_rate).yellow().bold().to_string()
+        } else {
+            format!("{:.2}%", branch_rate).red().bold().to_string()
+        };
+        output.push_str(&format!(
+            "Branches:                   {} / {} ({})\n",
+            report.totals.branch_hit, report.totals.branch_total, branch_rate_text
+        ));
+    }
+
+    if let Some(function_rate) = report.totals.function_rate {
+        let function_rate_text = if function_rate >= 80.0 {
+            format!("{:.2}%", function_rate).green().bold().to_string()
+        } else if function_rate >= 50.0 {
+            format!("{:.2}%", function_rate).yellow().bold().to_string()
+        } else {
+            format!("{:.2}%", function_rate).red().bold().to_string()
+        };
+        output.push_str(&format!(
+            "Functions:                  {} / {} ({})\n",
+            report.totals.function_hit, report.totals.function_total, function_rate_text
+        ));
+    }
+    output.push('\n');
 
     if !report.files.is_empty() {
         output.push_str(&"FILE COVERAGE".green().bold().to_string());
@@ -165,6 +194,36 @@ pub fn format_file_coverage(file: &FileCoverage, indent: usize) -> String {
         rate_text
     ));
 
+    if let Some(branch_rate) = file.summary.branch_rate {
+        let branch_rate_text = if branch_rate >= 80.0 {
+            format!("{:.2}%", branch_rate).green().bold().to_string()
+        } else if branch_rate >= 50.0 {
+            format!("{:.2}%", branch_rate).yellow().bold().to_string()
+        } else {
+            format!("{:.2}%", branch_rate).red().bold().to_string()
+        };
+        output.push_str(&spaces);
+        output.push_str(&format!(
+            "    Branches:   {} / {} ({})\n",
+            file.summary.branch_hit, file.summary.branch_total, branch_rate_text
+        ));
+    }
+
+    if let Some(function_rate) = file.summary.function_rate {
+        let function_rate_text = if function_rate >= 80.0 {
+            format!("{:.2}%", function_rate).green().bold().to_string()
+        } else if function_rate >= 50.0 {
+            format!("{:.2}%", function_rate).yellow().bold().to_string()
+        } else {
+            format!("{:.2}%", function_rate).red().bold().to_string()
+        };
+        output.push_str(&spaces);
+        output.push_str(&format!(
+            "    Functions:  {} / {} ({})\n",
+            file.summary.function_hit, file.summary.function_total, function_rate_text
+        ));
+    }
+
     if !file.miss_ranges.is_empty() {
         output.push_str(&uncovered_prefix);
         let max_width = 80 - uncovered_prefix.len();
@@ -182,6 +241,50 @@ pub fn format_file_coverage(file: &FileCoverage, indent: usize) -> String {
     output
 }
 
+/// Check a report against CI-style coverage floors. Returns `None` when both
+/// the total rate and every file's rate clear their respective minimums, or
+/// `Some(summary)` (colored like [`report_coverage`]'s rate lines) naming
+/// which ones didn't.
+pub fn check_thresholds(report: &CoverageReport, fail_under: Option<f64>, fail_under_file: Option<f64>) -> Option<String> {
+    let mut breaches: Vec<String> = Vec::new();
+
+    if let Some(min_total) = fail_under {
+        if report.totals.rate < min_total {
+            breaches.push(format!(
+                "Total coverage {} is below the required {:.2}%",
+                format!("{:.2}%", report.totals.rate).red().bold(),
+                min_total
+            ));
+        }
+    }
+
+    if let Some(min_file) = fail_under_file {
+        for file in &report.files {
+            if file.summary.rate < min_file {
+                breaches.push(format!(
+                    "{} is at {}, below the required {:.2}%",
+                    file.path.bold(),
+                    format!("{:.2}%", file.summary.rate).red().bold(),
+                    min_file
+                ));
+            }
+        }
+    }
+
+    if breaches.is_empty() {
+        return None;
+    }
+
+    let mut output = String::new();
+    output.push_str(&"COVERAGE THRESHOLD FAILED".red().bold().to_string());
+    output.push('\n');
+    for breach in breaches {
+        output.push_str(&format!("  - {}\n", breach));
+    }
+
+    Some(output)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -234,6 +337,66 @@ mod tests {
         assert!(output.contains("Uncovered:  2, 4-5"));
     }
 
+    #[test]
+    fn test_format_file_coverage_with_branches() {
+        let mut lines = BTreeMap::new();
+        lines.insert(1, 10);
+        lines.insert(2, 0);
+
+        let mut branches = BTreeMap::new();
+        branches.insert((1, 0, 0), Some(10));
+        branches.insert((1, 0, 1), None);
+
+        let file = FileCoverage::with_branches("test.rs".to_string(), lines, branches);
+        let output = format_file_coverage(&file, 2);
+        let output = strip_ansi_codes(&output);
+
+        assert!(output.contains("Branches:   1 / 2 (50.00%)"));
+    }
+
+    #[test]
+    fn test_format_file_coverage_without_branches_omits_line() {
+        let mut lines = BTreeMap::new();
+        lines.insert(1, 10);
+        lines.insert(2, 0);
+        lines.insert(3, 5);
+
+        let file = FileCoverage::new("test.rs".to_string(), lines);
+        let output = format_file_coverage(&file, 2);
+        let output = strip_ansi_codes(&output);
+
+        assert!(!output.contains("Branches:"));
+    }
+
+    #[test]
+    fn test_format_file_coverage_with_functions() {
+        let mut lines = BTreeMap::new();
+        lines.insert(1, 10);
+
+        let mut functions = BTreeMap::new();
+        functions.insert("foo".to_string(), 3);
+        functions.insert("bar".to_string(), 0);
+
+        let file =
+            FileCoverage::with_details("test.rs".to_string(), lines, BTreeMap::new(), functions);
+        let output = format_file_coverage(&file, 2);
+        let output = strip_ansi_codes(&output);
+
+        assert!(output.contains("Functions:  1 / 2 (50.00%)"));
+    }
+
+    #[test]
+    fn test_format_file_coverage_without_functions_omits_line() {
+        let mut lines = BTreeMap::new();
+        lines.insert(1, 10);
+
+        let file = FileCoverage::new("test.rs".to_string(), lines);
+        let output = format_file_coverage(&file, 2);
+        let output = strip_ansi_codes(&output);
+
+        assert!(!output.contains("Functions:"));
+    }
+
     #[test]
     fn test_format_file_coverage_full() {
         let mut lines = BTreeMap::new();
@@ -249,4 +412,39 @@ mod tests {
         assert!(output.contains("3 / 3 (100.00%)"));
         assert!(!output.contains("Uncovered"));
     }
+
+    #[test]
+    fn test_check_thresholds_passes_when_no_floors_set() {
+        let report = CoverageReport::new(vec![]);
+        assert!(check_thresholds(&report, None, None).is_none());
+    }
+
+    #[test]
+    fn test_check_thresholds_flags_total_breach() {
+        let mut lines = BTreeMap::new();
+        lines.insert(1, 0);
+        let report = CoverageReport::new(vec![FileCoverage::new("a.rs".to_string(), lines)]);
+
+        let result = check_thresholds(&report, Some(80.0), None);
+        let result = strip_ansi_codes(&result.unwrap());
+        assert!(result.contains("Total coverage"));
+    }
+
+    #[test]
+    fn test_check_thresholds_flags_per_file_breach() {
+        let mut good = BTreeMap::new();
+        good.insert(1, 10);
+        let mut bad = BTreeMap::new();
+        bad.insert(1, 0);
+
+        let report = CoverageReport::new(vec![
+            FileCoverage::new("good.rs".to_string(), good),
+            FileCoverage::new("bad.rs".to_string(), bad),
+        ]);
+
+        let result = check_thresholds(&report, None, Some(50.0));
+        let result = strip_ansi_codes(&result.unwrap());
+        assert!(result.contains("bad.rs"));
+        assert!(!result.contains("good.rs is at"));
+    }
 }
@@ -53,6 +53,60 @@ impl Report {
         serde_json::to_string_pretty(self)
     }
 
+    /// Serialize to a SARIF 2.1.0 document for code-scanning ingestion (e.g.
+    /// GitHub code scanning), with one result per function whose cyclomatic
+    /// complexity exceeds `warning_threshold`/`error_threshold`.
+    pub fn to_sarif(&self, warning_threshold: usize, error_threshold: usize) -> serde_json::Result<String> {
+        let mut results = Vec::new();
+
+        for file in &self.files {
+            for func in &file.cyclomatic.functions {
+                let level = if func.complexity > error_threshold {
+                    "error"
+                } else if func.complexity > warning_threshold {
+                    "warning"
+                } else {
+                    continue;
+                };
+
+                results.push(SarifResult {
+                    rule_id: "cyclomatic-complexity".to_string(),
+                    level: level.to_string(),
+                    message: SarifText { text: format!("{} has cyclomatic complexity {}", func.name, func.complexity) },
+                    locations: vec![SarifLocation {
+                        physical_location: SarifPhysicalLocation {
+                            artifact_location: SarifArtifactLocation { uri: file.path.display().to_string() },
+                            region: SarifRegion { start_line: func.line },
+                        },
+                    }],
+                });
+            }
+        }
+
+        let log = SarifLog {
+            schema: "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json"
+                .to_string(),
+            version: "2.1.0".to_string(),
+            runs: vec![SarifRun {
+                tool: SarifTool {
+                    driver: SarifDriver {
+                        name: "mccabre".to_string(),
+                        rules: vec![SarifRule {
+                            id: "cyclomatic-complexity".to_string(),
+                            name: "CyclomaticComplexity".to_string(),
+                            short_description: SarifText {
+                                text: "Cyclomatic complexity exceeds the configured threshold".to_string(),
+                            },
+                        }],
+                    },
+                },
+                results,
+            }],
+        };
+
+        serde_json::to_string_pretty(&log)
+    }
+
     /// Generate plaintext report
     pub fn to_plaintext(&self) -> String {
         let mut output = String::new();
@@ -159,6 +213,78 @@ impl Report {
     }
 }
 
+/// A SARIF 2.1.0 log: the root document uploaded to code-scanning tools.
+#[derive(Debug, Clone, Serialize)]
+struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: String,
+    version: String,
+    runs: Vec<SarifRun>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SarifRun {
+    tool: SarifTool,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SarifTool {
+    driver: SarifDriver,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SarifDriver {
+    name: String,
+    rules: Vec<SarifRule>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SarifRule {
+    id: String,
+    name: String,
+    #[serde(rename = "shortDescription")]
+    short_description: SarifText,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SarifText {
+    text: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SarifResult {
+    #[serde(rename = "ruleId")]
+    rule_id: String,
+    level: String,
+    message: SarifText,
+    locations: Vec<SarifLocation>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SarifLocation {
+    #[serde(rename = "physicalLocation")]
+    physical_location: SarifPhysicalLocation,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SarifPhysicalLocation {
+    #[serde(rename = "artifactLocation")]
+    artifact_location: SarifArtifactLocation,
+    region: SarifRegion,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SarifArtifactLocation {
+    uri: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SarifRegion {
+    #[serde(rename = "startLine")]
+    start_line: usize,
+}
+
 impl Summary {
     fn from_files(files: &[FileReport], clones: &[Clone]) -> Self {
         let total_files = files.len();
@@ -245,7 +371,7 @@ mod tests {
             loc: LocMetrics { physical: 10, logical: 8, comments: 1, blank: 1 },
             cyclomatic: CyclomaticMetrics {
                 file_complexity: 3,
-                functions: vec![FunctionComplexity { name: "test".to_string(), complexity: 3, line: 1 }],
+                functions: vec![FunctionComplexity { name: "test".to_string(), complexity: 3, line: 1, end_line: 5 }],
             },
         }];
 
@@ -256,4 +382,28 @@ mod tests {
         assert!(plaintext.contains("test.rs"));
         assert!(plaintext.contains("Cyclomatic Complexity"));
     }
+
+    #[test]
+    fn test_to_sarif_flags_functions_over_threshold() {
+        let files = vec![FileReport {
+            path: PathBuf::from("test.rs"),
+            loc: LocMetrics { physical: 10, logical: 8, comments: 1, blank: 1 },
+            cyclomatic: CyclomaticMetrics {
+                file_complexity: 25,
+                functions: vec![
+                    FunctionComplexity { name: "ok".to_string(), complexity: 3, line: 1, end_line: 2 },
+                    FunctionComplexity { name: "risky".to_string(), complexity: 25, line: 5, end_line: 10 },
+                ],
+            },
+        }];
+
+        let report = Report::new(files, vec![]);
+        let sarif = report.to_sarif(10, 20).unwrap();
+
+        assert!(sarif.contains("\"version\": \"2.1.0\""));
+        assert!(sarif.contains("cyclomatic-complexity"));
+        assert!(sarif.contains("\"level\": \"error\""));
+        assert!(sarif.contains("risky"));
+        assert!(!sarif.contains("\"ok has cyclomatic complexity"));
+    }
 }
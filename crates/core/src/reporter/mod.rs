@@ -1,9 +1,15 @@
 pub mod coverage_detailed;
+pub mod coverage_hotspots;
+pub mod coverage_html;
 pub mod coverage_jsonl;
+pub mod coverage_summary;
 pub mod coverage_term;
 pub mod legacy;
 
 pub use coverage_detailed::{report_detailed_file_view, report_directory_view};
+pub use coverage_hotspots::{Hotspot, find_hotspots, report_hotspots};
+pub use coverage_html::HtmlReporter;
 pub use coverage_jsonl::JsonlReporter;
-pub use coverage_term::{format_file_coverage, report_coverage};
+pub use coverage_summary::report_directory_summary;
+pub use coverage_term::{check_thresholds, format_file_coverage, report_coverage};
 pub use legacy::{FileReport, Report, Summary};
@@ -1,7 +1,8 @@
 use crate::error::{MccabreError, Result};
+use std::ops::Range;
 use std::path::Path;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Language {
     Rust,
     JavaScript,
@@ -30,6 +31,95 @@ impl Language {
         }
     }
 
+    /// Detect a language from its leading shebang line (`#!/usr/bin/env node`)
+    /// or, failing that, a handful of content heuristics. This only runs once
+    /// [`Language::from_path`] has already failed, so it's marked `#[cold]`
+    /// to keep it off the common extension-match path.
+    #[cold]
+    pub fn from_content(content: &str) -> Option<Self> {
+        let first_line = content.lines().next().unwrap_or("");
+
+        if let Some(rest) = first_line.strip_prefix("#!") {
+            let mut parts = rest.trim().split_whitespace();
+            let mut interpreter = parts.next().unwrap_or("");
+            if interpreter.ends_with("/env") || interpreter == "env" {
+                interpreter = parts.next().unwrap_or("");
+            } else {
+                interpreter = interpreter.rsplit('/').next().unwrap_or(interpreter);
+            }
+
+            match interpreter {
+                "node" | "deno" | "bun" => return Some(Language::JavaScript),
+                "ts-node" => return Some(Language::TypeScript),
+                _ => {}
+            }
+        }
+
+        if content.contains("package main") || content.contains("func ") {
+            return Some(Language::Go);
+        }
+
+        if content.contains("fn main") || content.contains("use ") {
+            return Some(Language::Rust);
+        }
+
+        None
+    }
+
+    /// Detect a file's language, preferring the cheap extension check and
+    /// falling back to [`Language::from_content`] for extensionless files
+    /// (scripts with a shebang, extensionless Go/Rust tooling files, etc.).
+    pub fn detect(path: &Path, content: &str) -> Result<Self> {
+        Language::from_path(path).or_else(|_| {
+            Language::from_content(content)
+                .ok_or_else(|| MccabreError::UnsupportedFileType(path.to_string_lossy().to_string()))
+        })
+    }
+
+    /// Parse a language name as accepted by the `--type`/`--type-not` flags
+    /// and the `files.languages` config list, e.g. `"rust"`, `"js"`, `"c++"`.
+    pub fn parse_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "rust" | "rs" => Some(Language::Rust),
+            "javascript" | "js" => Some(Language::JavaScript),
+            "typescript" | "ts" => Some(Language::TypeScript),
+            "go" | "golang" => Some(Language::Go),
+            "java" => Some(Language::Java),
+            "cpp" | "c++" | "cxx" => Some(Language::Cpp),
+            _ => None,
+        }
+    }
+
+    /// Canonical name for this language, the inverse of [`Language::parse_name`]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::Rust => "rust",
+            Language::JavaScript => "javascript",
+            Language::TypeScript => "typescript",
+            Language::Go => "go",
+            Language::Java => "java",
+            Language::Cpp => "cpp",
+        }
+    }
+
+    /// Parse a list of language names, e.g. the `--type`/`--type-not` flag
+    /// values or a `files.languages`/`files.languages_exclude` config list,
+    /// failing on the first name that doesn't match [`Language::parse_name`].
+    pub fn parse_many<I, S>(names: I) -> Result<Vec<Self>>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        names
+            .into_iter()
+            .map(|name| {
+                let name = name.as_ref();
+                Language::parse_name(name)
+                    .ok_or_else(|| MccabreError::InvalidConfig(format!("unknown language `{name}`")))
+            })
+            .collect()
+    }
+
     /// Get single-line comment prefix
     pub fn single_line_comment(&self) -> &'static str {
         match self {
@@ -55,11 +145,10 @@ impl Language {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum TokenType {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenType<'a> {
     If,
     Else,
-    ElseIf,
     While,
     For,
     Loop,
@@ -68,15 +157,17 @@ pub enum TokenType {
     Case,
     Default,
     Catch,
+    Select,
 
     LogicalAnd,
     LogicalOr,
     Ternary,
+    OptionalChaining,
 
-    Operator(String),
+    Operator(&'a str),
 
-    Identifier(String),
-    Literal(String),
+    Identifier(&'a str),
+    Literal(&'a str),
 
     LeftBrace,
     RightBrace,
@@ -93,24 +184,25 @@ pub enum TokenType {
     Unknown(char),
 }
 
-impl TokenType {
-    /// Returns true if this token contributes to cyclomatic complexity
-    pub fn is_decision_point(&self) -> bool {
-        matches!(
-            self,
-            TokenType::If
-                | TokenType::ElseIf
-                | TokenType::While
-                | TokenType::For
-                | TokenType::Loop
-                | TokenType::Match
-                | TokenType::Switch
-                | TokenType::Case
-                | TokenType::Catch
-                | TokenType::LogicalAnd
-                | TokenType::LogicalOr
-                | TokenType::Ternary
-        )
+impl<'a> TokenType<'a> {
+    /// Returns true if this token contributes to cyclomatic complexity for `language`.
+    ///
+    /// Each language only contributes the control-flow constructs it actually has:
+    /// Go has no `while`/`match`/`catch`/ternary but does have `select`; Rust has no
+    /// `switch`/`case`/`catch`; only JS/TS have optional-chaining (`?.`).
+    pub fn is_decision_point(&self, language: Language) -> bool {
+        match self {
+            TokenType::If | TokenType::For => true,
+            TokenType::While => !matches!(language, Language::Go),
+            TokenType::Loop | TokenType::Match => matches!(language, Language::Rust),
+            TokenType::Switch | TokenType::Case => !matches!(language, Language::Rust),
+            TokenType::Select => matches!(language, Language::Go),
+            TokenType::Catch => !matches!(language, Language::Rust | Language::Go),
+            TokenType::Ternary => !matches!(language, Language::Go),
+            TokenType::LogicalAnd | TokenType::LogicalOr => !matches!(language, Language::Go),
+            TokenType::OptionalChaining => matches!(language, Language::JavaScript | Language::TypeScript),
+            _ => false,
+        }
     }
 
     /// Returns true if this token should be included in clone detection
@@ -120,27 +212,36 @@ impl TokenType {
 }
 
 #[derive(Debug, Clone)]
-pub struct Token {
-    pub token_type: TokenType,
+pub struct Token<'a> {
+    pub token_type: TokenType<'a>,
     pub line: usize,
     pub column: usize,
-    pub text: String,
+    pub text: &'a str,
+    /// Byte range of this token within the original source, so downstream
+    /// consumers (clone detection, reporting) can point back into the file
+    /// without re-tokenizing.
+    pub span: Range<usize>,
 }
 
-pub struct Tokenizer {
-    source: Vec<char>,
+pub struct Tokenizer<'a> {
+    source: &'a str,
     position: usize,
     line: usize,
     column: usize,
-    _language: Language,
+    language: Language,
+    /// Stack of brace-nesting depths for currently-open `${ ... }` template
+    /// literal interpolations (JS/TS only), innermost last. A depth of `0`
+    /// means the next unmatched `}` closes that interpolation and resumes
+    /// template-text scanning rather than emitting a `RightBrace` token.
+    template_interp_depth: Vec<u32>,
 }
 
-impl Tokenizer {
-    pub fn new(source: &str, language: Language) -> Self {
-        Self { source: source.chars().collect(), position: 0, line: 1, column: 1, _language: language }
+impl<'a> Tokenizer<'a> {
+    pub fn new(source: &'a str, language: Language) -> Self {
+        Self { source, position: 0, line: 1, column: 1, language, template_interp_depth: Vec::new() }
     }
 
-    pub fn tokenize(mut self) -> Result<Vec<Token>> {
+    pub fn tokenize(mut self) -> Result<Vec<Token<'a>>> {
         let mut tokens = Vec::new();
 
         while !self.is_at_end() {
@@ -152,7 +253,7 @@ impl Tokenizer {
         Ok(tokens)
     }
 
-    fn next_token(&mut self) -> Result<Option<Token>> {
+    fn next_token(&mut self) -> Result<Option<Token<'a>>> {
         let start_line = self.line;
         let start_column = self.column;
         let start_pos = self.position;
@@ -165,7 +266,8 @@ impl Tokenizer {
                     token_type: TokenType::Newline,
                     line: start_line,
                     column: start_column,
-                    text: "\n".to_string(),
+                    text: self.slice(start_pos, self.position),
+                    span: start_pos..self.position,
                 }));
             } else {
                 while !self.is_at_end() && self.current()?.is_whitespace() && self.current()? != '\n' {
@@ -175,7 +277,8 @@ impl Tokenizer {
                     token_type: TokenType::Whitespace,
                     line: start_line,
                     column: start_column,
-                    text: " ".to_string(),
+                    text: self.slice(start_pos, self.position),
+                    span: start_pos..self.position,
                 }));
             }
         }
@@ -189,51 +292,28 @@ impl Tokenizer {
                     token_type: TokenType::Comment,
                     line: start_line,
                     column: start_column,
-                    text: "//".to_string(),
+                    text: self.slice(start_pos, self.position),
+                    span: start_pos..self.position,
                 }));
             } else if self.peek() == Some('*') {
-                self.advance();
-                self.advance();
-                while !self.is_at_end() {
-                    if self.current()? == '*' && self.peek() == Some('/') {
-                        self.advance();
-                        self.advance();
-                        break;
-                    }
-                    self.advance();
-                }
-                return Ok(Some(Token {
-                    token_type: TokenType::Comment,
-                    line: start_line,
-                    column: start_column,
-                    text: "/**/".to_string(),
-                }));
+                return Ok(Some(self.scan_block_comment(start_pos, start_line, start_column)?));
             }
         }
 
         if ch == '"' || ch == '\'' {
-            let quote = ch;
+            return Ok(Some(self.scan_string(ch, start_pos, start_line, start_column)?));
+        }
+
+        if ch == '`' && matches!(self.language, Language::JavaScript | Language::TypeScript) {
             self.advance();
-            while !self.is_at_end() && self.current()? != quote {
-                if self.current()? == '\\' {
-                    self.advance();
-                    if !self.is_at_end() {
-                        self.advance();
-                    }
-                } else {
-                    self.advance();
-                }
-            }
-            if !self.is_at_end() {
-                self.advance();
-            }
-            let text: String = self.source[start_pos..self.position].iter().collect();
-            return Ok(Some(Token {
-                token_type: TokenType::Literal(text.clone()),
-                line: start_line,
-                column: start_column,
-                text,
-            }));
+            return Ok(Some(self.scan_template_chunk(start_pos, start_line, start_column)?));
+        }
+
+        if ch == '}' && matches!(self.template_interp_depth.last(), Some(0)) {
+            self.advance();
+            self.template_interp_depth.pop();
+            let chunk_start = self.position;
+            return Ok(Some(self.scan_template_chunk(chunk_start, self.line, self.column)?));
         }
 
         if ch.is_ascii_digit() {
@@ -242,12 +322,13 @@ impl Tokenizer {
             {
                 self.advance();
             }
-            let text: String = self.source[start_pos..self.position].iter().collect();
+            let text = self.slice(start_pos, self.position);
             return Ok(Some(Token {
-                token_type: TokenType::Literal(text.clone()),
+                token_type: TokenType::Literal(text),
                 line: start_line,
                 column: start_column,
                 text,
+                span: start_pos..self.position,
             }));
         }
 
@@ -255,18 +336,29 @@ impl Tokenizer {
             while !self.is_at_end() && (self.current()?.is_alphanumeric() || self.current()? == '_') {
                 self.advance();
             }
-            let text: String = self.source[start_pos..self.position].iter().collect();
-            let token_type = self.classify_keyword(&text);
-            return Ok(Some(Token { token_type, line: start_line, column: start_column, text }));
+            let text = self.slice(start_pos, self.position);
+
+            if let Some(token) = self.maybe_scan_prefixed_literal(text, start_pos, start_line, start_column)? {
+                return Ok(Some(token));
+            }
+
+            let token_type = self.classify_keyword(text);
+            return Ok(Some(Token { token_type, line: start_line, column: start_column, text, span: start_pos..self.position }));
         }
 
         let token_type = match ch {
             '{' => {
                 self.advance();
+                if let Some(depth) = self.template_interp_depth.last_mut() {
+                    *depth += 1;
+                }
                 TokenType::LeftBrace
             }
             '}' => {
                 self.advance();
+                if let Some(depth) = self.template_interp_depth.last_mut() {
+                    *depth -= 1;
+                }
                 TokenType::RightBrace
             }
             '(' => {
@@ -293,6 +385,11 @@ impl Tokenizer {
                 self.advance();
                 TokenType::Comma
             }
+            '?' if self.peek() == Some('.') => {
+                self.advance();
+                self.advance();
+                TokenType::OptionalChaining
+            }
             '?' => {
                 self.advance();
                 TokenType::Ternary
@@ -313,8 +410,7 @@ impl Tokenizer {
                     while !self.is_at_end() && op_chars.contains(self.current()?) {
                         self.advance();
                     }
-                    let text: String = self.source[start_pos..self.position].iter().collect();
-                    TokenType::Operator(text)
+                    TokenType::Operator(self.slice(start_pos, self.position))
                 } else {
                     self.advance();
                     TokenType::Unknown(ch)
@@ -322,47 +418,248 @@ impl Tokenizer {
             }
         };
 
-        let text: String = self.source[start_pos..self.position].iter().collect();
-        Ok(Some(Token { token_type, line: start_line, column: start_column, text }))
+        let text = self.slice(start_pos, self.position);
+        Ok(Some(Token { token_type, line: start_line, column: start_column, text, span: start_pos..self.position }))
+    }
+
+    /// Scan a `/* ... */` block comment. Rarer than line comments and
+    /// identifiers, so it's split out of the hot dispatch path in [`Tokenizer::next_token`].
+    ///
+    /// Rust allows `/* */` comments to nest (`/* outer /* inner */ still outer */`),
+    /// so for [`Language::Rust`] we track nesting depth; every other supported
+    /// language treats the first `*/` as the end, matching their real grammars.
+    #[cold]
+    fn scan_block_comment(&mut self, start_pos: usize, start_line: usize, start_column: usize) -> Result<Token<'a>> {
+        self.advance();
+        self.advance();
+        let nests = matches!(self.language, Language::Rust);
+        let mut depth: u32 = 1;
+        while !self.is_at_end() {
+            if nests && self.current()? == '/' && self.peek() == Some('*') {
+                self.advance();
+                self.advance();
+                depth += 1;
+            } else if self.current()? == '*' && self.peek() == Some('/') {
+                self.advance();
+                self.advance();
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            } else {
+                self.advance();
+            }
+        }
+        let text = self.slice(start_pos, self.position);
+        Ok(Token { token_type: TokenType::Comment, line: start_line, column: start_column, text, span: start_pos..self.position })
+    }
+
+    /// Scan a quoted string/char literal, honoring backslash escapes. Kept off
+    /// the hot dispatch path in [`Tokenizer::next_token`] since escape handling
+    /// is rarely hit relative to the identifier/operator fast paths.
+    #[cold]
+    fn scan_string(&mut self, quote: char, start_pos: usize, start_line: usize, start_column: usize) -> Result<Token<'a>> {
+        self.advance();
+        while !self.is_at_end() && self.current()? != quote {
+            if self.current()? == '\\' {
+                self.advance();
+                if !self.is_at_end() {
+                    self.advance();
+                }
+            } else {
+                self.advance();
+            }
+        }
+        if !self.is_at_end() {
+            self.advance();
+        }
+        let text = self.slice(start_pos, self.position);
+        Ok(Token { token_type: TokenType::Literal(text), line: start_line, column: start_column, text, span: start_pos..self.position })
+    }
+
+    /// If `word` is a string-prefix recognized by `self.language` (Rust's
+    /// `r`/`b`/`br`/`rb`, C++'s `R`) and it's immediately followed by the
+    /// delimiter that prefix expects, scan the whole prefixed literal and
+    /// return it. Otherwise `word` was just an ordinary identifier.
+    fn maybe_scan_prefixed_literal(
+        &mut self, word: &'a str, start_pos: usize, start_line: usize, start_column: usize,
+    ) -> Result<Option<Token<'a>>> {
+        match self.language {
+            Language::Rust => match word {
+                "r" | "br" | "rb" if matches!(self.current().ok(), Some('"') | Some('#')) => {
+                    Ok(Some(self.scan_rust_raw_string(start_pos, start_line, start_column)?))
+                }
+                "b" if self.current().ok() == Some('"') => {
+                    Ok(Some(self.scan_string('"', start_pos, start_line, start_column)?))
+                }
+                _ => Ok(None),
+            },
+            Language::Cpp if word == "R" && self.current().ok() == Some('"') => {
+                Ok(Some(self.scan_cpp_raw_string(start_pos, start_line, start_column)?))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Scan a Rust raw string (`r"..."`, `r#"..."#`, `r##"..."##`, ...), with
+    /// an optional leading `b`/`rb` byte-string prefix already consumed by the
+    /// caller. The closing delimiter must repeat the same `#` count as the
+    /// opening one, so unlike a plain quoted string this can't be scanned by
+    /// just looking for the next unescaped quote.
+    #[cold]
+    fn scan_rust_raw_string(&mut self, start_pos: usize, start_line: usize, start_column: usize) -> Result<Token<'a>> {
+        let mut hash_count = 0usize;
+        while self.current().ok() == Some('#') {
+            self.advance();
+            hash_count += 1;
+        }
+        if self.current().ok() == Some('"') {
+            self.advance();
+        }
+        let closing: String = std::iter::once('"').chain(std::iter::repeat('#').take(hash_count)).collect();
+        while !self.is_at_end() {
+            if self.source[self.position..].starts_with(&closing) {
+                for _ in 0..closing.chars().count() {
+                    self.advance();
+                }
+                break;
+            }
+            self.advance();
+        }
+        let text = self.slice(start_pos, self.position);
+        Ok(Token { token_type: TokenType::Literal(text), line: start_line, column: start_column, text, span: start_pos..self.position })
+    }
+
+    /// Scan a C++ raw string `R"delim(...)delim"`, where `delim` is an
+    /// arbitrary (possibly empty) tag chosen by the author and repeated on
+    /// both sides of the payload.
+    #[cold]
+    fn scan_cpp_raw_string(&mut self, start_pos: usize, start_line: usize, start_column: usize) -> Result<Token<'a>> {
+        self.advance();
+        let delim_start = self.position;
+        while !self.is_at_end() && self.current()? != '(' {
+            self.advance();
+        }
+        let delimiter = self.slice(delim_start, self.position);
+        if self.current().ok() == Some('(') {
+            self.advance();
+        }
+        let mut closing = String::from(")");
+        closing.push_str(delimiter);
+        closing.push('"');
+        while !self.is_at_end() {
+            if self.source[self.position..].starts_with(&closing) {
+                for _ in 0..closing.chars().count() {
+                    self.advance();
+                }
+                break;
+            }
+            self.advance();
+        }
+        let text = self.slice(start_pos, self.position);
+        Ok(Token { token_type: TokenType::Literal(text), line: start_line, column: start_column, text, span: start_pos..self.position })
+    }
+
+    /// Scan one chunk of a JS/TS template literal: the literal text between
+    /// either the opening backtick (or a previous `}` that closed an
+    /// interpolation) and whichever comes first of the closing backtick or
+    /// the next `${`. Hitting `${` pushes a fresh interpolation-depth marker
+    /// and stops, so the interpolated expression that follows is tokenized as
+    /// ordinary code rather than absorbed into the literal's text.
+    #[cold]
+    fn scan_template_chunk(&mut self, start_pos: usize, start_line: usize, start_column: usize) -> Result<Token<'a>> {
+        while !self.is_at_end() {
+            let c = self.current()?;
+            if c == '\\' {
+                self.advance();
+                if !self.is_at_end() {
+                    self.advance();
+                }
+                continue;
+            }
+            if c == '`' {
+                self.advance();
+                break;
+            }
+            if c == '$' && self.peek() == Some('{') {
+                self.advance();
+                self.advance();
+                self.template_interp_depth.push(0);
+                break;
+            }
+            self.advance();
+        }
+        let text = self.slice(start_pos, self.position);
+        Ok(Token { token_type: TokenType::Literal(text), line: start_line, column: start_column, text, span: start_pos..self.position })
     }
 
-    fn classify_keyword(&self, word: &str) -> TokenType {
+    fn classify_keyword(&self, word: &'a str) -> TokenType<'a> {
         match word {
             "if" => TokenType::If,
             "else" => TokenType::Else,
-            "elif" => TokenType::ElseIf,
-            "while" => TokenType::While,
             "for" => TokenType::For,
-            "loop" => TokenType::Loop,
-            "match" => TokenType::Match,
-            "switch" => TokenType::Switch,
-            "case" => TokenType::Case,
-            "default" => TokenType::Default,
-            "catch" => TokenType::Catch,
-            _ => TokenType::Identifier(word.to_string()),
+            _ => self.classify_language_keyword(word),
         }
     }
 
+    /// Keywords specific to `self.language`'s control-flow vocabulary, e.g. Go's
+    /// `select` or Rust's `loop`/`match` (which no other supported language has).
+    fn classify_language_keyword(&self, word: &'a str) -> TokenType<'a> {
+        match self.language {
+            Language::Rust => match word {
+                "while" => TokenType::While,
+                "loop" => TokenType::Loop,
+                "match" => TokenType::Match,
+                _ => TokenType::Identifier(word),
+            },
+            Language::Go => match word {
+                "switch" => TokenType::Switch,
+                "case" => TokenType::Case,
+                "default" => TokenType::Default,
+                "select" => TokenType::Select,
+                _ => TokenType::Identifier(word),
+            },
+            Language::Java | Language::Cpp | Language::JavaScript | Language::TypeScript => match word {
+                "while" => TokenType::While,
+                "switch" => TokenType::Switch,
+                "case" => TokenType::Case,
+                "default" => TokenType::Default,
+                "catch" => TokenType::Catch,
+                _ => TokenType::Identifier(word),
+            },
+        }
+    }
+
+    /// Borrow `[start, end)` directly out of the original source, decoupled
+    /// from `&self`'s borrow so the slice can outlive this call and carry the
+    /// source's own `'a` lifetime instead of being copied into an owned `String`.
+    fn slice(&self, start: usize, end: usize) -> &'a str {
+        let source: &'a str = self.source;
+        &source[start..end]
+    }
+
     fn current(&self) -> Result<char> {
-        self.source
-            .get(self.position)
-            .copied()
+        self.source[self.position..]
+            .chars()
+            .next()
             .ok_or_else(|| MccabreError::TokenizationError("Unexpected end of input".to_string()))
     }
 
     fn peek(&self) -> Option<char> {
-        self.source.get(self.position + 1).copied()
+        let mut chars = self.source[self.position..].chars();
+        chars.next()?;
+        chars.next()
     }
 
     fn advance(&mut self) {
-        if let Some(ch) = self.source.get(self.position) {
-            if *ch == '\n' {
+        if let Some(ch) = self.source[self.position..].chars().next() {
+            if ch == '\n' {
                 self.line += 1;
                 self.column = 1;
             } else {
                 self.column += 1;
             }
-            self.position += 1;
+            self.position += ch.len_utf8();
         }
     }
 
@@ -385,6 +682,65 @@ mod tests {
         assert_eq!(Language::from_path(Path::new("test.cpp")).unwrap(), Language::Cpp);
     }
 
+    #[test]
+    fn test_from_content_detects_shebang_interpreters() {
+        assert_eq!(Language::from_content("#!/usr/bin/env node\nconsole.log(1);"), Some(Language::JavaScript));
+        assert_eq!(Language::from_content("#!/usr/bin/env deno run\n"), Some(Language::JavaScript));
+        assert_eq!(Language::from_content("#!/usr/bin/node\n"), Some(Language::JavaScript));
+        assert_eq!(Language::from_content("#!/usr/bin/env ts-node\n"), Some(Language::TypeScript));
+        assert_eq!(Language::from_content("#!/usr/bin/env python3\n"), None);
+    }
+
+    #[test]
+    fn test_from_content_detects_go_and_rust_heuristics() {
+        assert_eq!(Language::from_content("package main\n\nfunc main() {}\n"), Some(Language::Go));
+        assert_eq!(Language::from_content("use std::io;\n\nfn main() {}\n"), Some(Language::Rust));
+        assert_eq!(Language::from_content("just some plain text\n"), None);
+    }
+
+    #[test]
+    fn test_detect_falls_back_to_content_for_extensionless_files() {
+        let language = Language::detect(Path::new("build-tool"), "#!/usr/bin/env node\n").unwrap();
+        assert_eq!(language, Language::JavaScript);
+
+        assert!(Language::detect(Path::new("build-tool"), "just plain text\n").is_err());
+    }
+
+    #[test]
+    fn test_detect_prefers_extension_over_content() {
+        let language = Language::detect(Path::new("script.rs"), "package main\n").unwrap();
+        assert_eq!(language, Language::Rust);
+    }
+
+    #[test]
+    fn test_parse_name_accepts_aliases_case_insensitively() {
+        assert_eq!(Language::parse_name("Rust"), Some(Language::Rust));
+        assert_eq!(Language::parse_name("js"), Some(Language::JavaScript));
+        assert_eq!(Language::parse_name("TYPESCRIPT"), Some(Language::TypeScript));
+        assert_eq!(Language::parse_name("c++"), Some(Language::Cpp));
+        assert_eq!(Language::parse_name("cobol"), None);
+    }
+
+    #[test]
+    fn test_name_round_trips_through_parse_name() {
+        for lang in [
+            Language::Rust,
+            Language::JavaScript,
+            Language::TypeScript,
+            Language::Go,
+            Language::Java,
+            Language::Cpp,
+        ] {
+            assert_eq!(Language::parse_name(lang.name()), Some(lang));
+        }
+    }
+
+    #[test]
+    fn test_parse_many_collects_languages_and_rejects_unknown() {
+        assert_eq!(Language::parse_many(["rust", "js"]).unwrap(), vec![Language::Rust, Language::JavaScript]);
+        assert!(Language::parse_many(["rust", "cobol"]).is_err());
+    }
+
     #[test]
     fn test_tokenize_simple() {
         let source = "if (x > 5) { return true; }";
@@ -397,15 +753,80 @@ mod tests {
         assert!(tokens.iter().any(|t| matches!(t.token_type, TokenType::If)));
     }
 
+    #[test]
+    fn test_token_spans_point_back_into_source() {
+        let source = "if (x > 5) { }";
+        let tokenizer = Tokenizer::new(source, Language::Rust);
+        let tokens = tokenizer.tokenize().unwrap();
+
+        for token in &tokens {
+            assert_eq!(&source[token.span.clone()], token.text);
+        }
+    }
+
     #[test]
     fn test_decision_points() {
         let source = "if (x && y || z) { while (true) { } }";
         let tokenizer = Tokenizer::new(source, Language::Rust);
         let tokens = tokenizer.tokenize().unwrap();
-        let decision_count = tokens.iter().filter(|t| t.token_type.is_decision_point()).count();
+        let decision_count = tokens.iter().filter(|t| t.token_type.is_decision_point(Language::Rust)).count();
         assert_eq!(decision_count, 4);
     }
 
+    #[test]
+    fn test_decision_points_go_excludes_while_match_and_logical_operators() {
+        let source = "if x { for { switch x { case 1: } select { } } } while match && ||";
+        let tokenizer = Tokenizer::new(source, Language::Go);
+        let tokens = tokenizer.tokenize().unwrap();
+        let decision_count = tokens.iter().filter(|t| t.token_type.is_decision_point(Language::Go)).count();
+        assert_eq!(decision_count, 5);
+    }
+
+    #[test]
+    fn test_decision_points_java() {
+        let source = "if (x) { for (;;) { while (x) { switch (x) { case 1: break; } } } } try { } catch (e) { } a && b ? c : d";
+        let tokenizer = Tokenizer::new(source, Language::Java);
+        let tokens = tokenizer.tokenize().unwrap();
+        let decision_count = tokens.iter().filter(|t| t.token_type.is_decision_point(Language::Java)).count();
+        assert_eq!(decision_count, 8);
+    }
+
+    #[test]
+    fn test_decision_points_cpp() {
+        let source = "if (x) { for (;;) { while (x) { switch (x) { case 1: break; } } } } a || b";
+        let tokenizer = Tokenizer::new(source, Language::Cpp);
+        let tokens = tokenizer.tokenize().unwrap();
+        let decision_count = tokens.iter().filter(|t| t.token_type.is_decision_point(Language::Cpp)).count();
+        assert_eq!(decision_count, 6);
+    }
+
+    #[test]
+    fn test_decision_points_javascript_includes_optional_chaining() {
+        let source = "if (x) { while (x) { switch (x) { case 1: break; } } } catch (e) { } a?.b a && b ? c : d";
+        let tokenizer = Tokenizer::new(source, Language::JavaScript);
+        let tokens = tokenizer.tokenize().unwrap();
+        let decision_count = tokens.iter().filter(|t| t.token_type.is_decision_point(Language::JavaScript)).count();
+        assert_eq!(decision_count, 8);
+    }
+
+    #[test]
+    fn test_decision_points_typescript_includes_optional_chaining() {
+        let source = "if (x) { switch (x) { case 1: break; } } a?.b ? c : d";
+        let tokenizer = Tokenizer::new(source, Language::TypeScript);
+        let tokens = tokenizer.tokenize().unwrap();
+        let decision_count = tokens.iter().filter(|t| t.token_type.is_decision_point(Language::TypeScript)).count();
+        assert_eq!(decision_count, 5);
+    }
+
+    #[test]
+    fn test_optional_chaining_not_confused_with_ternary() {
+        let source = "a?.b";
+        let tokenizer = Tokenizer::new(source, Language::JavaScript);
+        let tokens = tokenizer.tokenize().unwrap();
+        assert!(tokens.iter().any(|t| matches!(t.token_type, TokenType::OptionalChaining)));
+        assert!(!tokens.iter().any(|t| matches!(t.token_type, TokenType::Ternary)));
+    }
+
     #[test]
     fn test_comments() {
         let source = r#"
@@ -438,4 +859,96 @@ let x = 5;
 
         assert!(literals.len() >= 2);
     }
+
+    #[test]
+    fn test_rust_raw_string_does_not_leak_decision_points() {
+        let source = r##"let pattern = r#"if x && y { do_it(); }"#;"##;
+        let tokenizer = Tokenizer::new(source, Language::Rust);
+        let tokens = tokenizer.tokenize().unwrap();
+
+        assert_eq!(tokens.iter().filter(|t| t.token_type.is_decision_point(Language::Rust)).count(), 0);
+        let literal = tokens.iter().find(|t| matches!(t.token_type, TokenType::Literal(_))).unwrap();
+        assert_eq!(literal.text, r##"r#"if x && y { do_it(); }"#"##);
+    }
+
+    #[test]
+    fn test_rust_byte_and_byte_raw_strings() {
+        let source = r##"let a = b"if && raw"; let b = br#"if && raw"#;"##;
+        let tokenizer = Tokenizer::new(source, Language::Rust);
+        let tokens = tokenizer.tokenize().unwrap();
+
+        assert_eq!(tokens.iter().filter(|t| t.token_type.is_decision_point(Language::Rust)).count(), 0);
+        let literals: Vec<_> =
+            tokens.iter().filter(|t| matches!(t.token_type, TokenType::Literal(_))).collect();
+        assert_eq!(literals.len(), 2);
+    }
+
+    #[test]
+    fn test_cpp_raw_string_with_delimiter_does_not_leak_decision_points() {
+        let source = r#"auto s = R"delim(if (x && y) { })delim";"#;
+        let tokenizer = Tokenizer::new(source, Language::Cpp);
+        let tokens = tokenizer.tokenize().unwrap();
+
+        assert_eq!(tokens.iter().filter(|t| t.token_type.is_decision_point(Language::Cpp)).count(), 0);
+        let literal = tokens.iter().find(|t| matches!(t.token_type, TokenType::Literal(_))).unwrap();
+        assert_eq!(literal.text, r#"R"delim(if (x && y) { })delim""#);
+    }
+
+    #[test]
+    fn test_nested_block_comments_in_rust() {
+        let source = "/* outer /* inner if x && y */ still outer */ let x = 1;";
+        let tokenizer = Tokenizer::new(source, Language::Rust);
+        let tokens = tokenizer.tokenize().unwrap();
+
+        let comments: Vec<_> =
+            tokens.iter().filter(|t| matches!(t.token_type, TokenType::Comment)).collect();
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].text, "/* outer /* inner if x && y */ still outer */");
+        assert_eq!(tokens.iter().filter(|t| t.token_type.is_decision_point(Language::Rust)).count(), 0);
+    }
+
+    #[test]
+    fn test_block_comments_do_not_nest_outside_rust() {
+        let source = "/* outer /* inner */ still outer */";
+        let tokenizer = Tokenizer::new(source, Language::Java);
+        let tokens = tokenizer.tokenize().unwrap();
+
+        let comments: Vec<_> =
+            tokens.iter().filter(|t| matches!(t.token_type, TokenType::Comment)).collect();
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].text, "/* outer /* inner */");
+    }
+
+    #[test]
+    fn test_template_literal_static_text_does_not_leak_decision_points() {
+        let source = r#"const s = `if (a && b) { return true; }`;"#;
+        let tokenizer = Tokenizer::new(source, Language::JavaScript);
+        let tokens = tokenizer.tokenize().unwrap();
+
+        assert_eq!(tokens.iter().filter(|t| t.token_type.is_decision_point(Language::JavaScript)).count(), 0);
+    }
+
+    #[test]
+    fn test_template_literal_interpolation_tokenizes_as_real_code() {
+        let source = "const s = `total: ${a && b ? 1 : 2}`;";
+        let tokenizer = Tokenizer::new(source, Language::TypeScript);
+        let tokens = tokenizer.tokenize().unwrap();
+
+        assert_eq!(tokens.iter().filter(|t| t.token_type.is_decision_point(Language::TypeScript)).count(), 2);
+        assert!(tokens.iter().any(|t| matches!(t.token_type, TokenType::Identifier(name) if name == "a")));
+    }
+
+    #[test]
+    fn test_nested_template_literal_interpolation() {
+        let source = "const s = `a${ `b${c}` }d`;";
+        let tokenizer = Tokenizer::new(source, Language::JavaScript);
+        let tokens = tokenizer.tokenize().unwrap();
+
+        let literals: Vec<_> =
+            tokens.iter().filter(|t| matches!(t.token_type, TokenType::Literal(_))).map(|t| t.text).collect();
+        assert!(literals.contains(&"`a${"));
+        assert!(literals.contains(&"`b${"));
+        assert!(literals.contains(&"`"));
+        assert!(literals.contains(&"d`"));
+    }
 }